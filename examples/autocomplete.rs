@@ -1,3 +1,4 @@
+use cliclack::Suggestion;
 use console::style;
 use std::io;
 
@@ -25,9 +26,35 @@ fn main() -> io::Result<()> {
 
     let language: String = cliclack::input("Pick a language")
         .autocomplete(languages)
+        .max_suggestions(4)
         .interact()?;
 
-    cliclack::outro(format!("Selected: {language}"))?;
+    let commands = vec![
+        Suggestion::new("build").description("Compile the current package"),
+        Suggestion::new("check").description("Analyze the package for errors"),
+        Suggestion::new("clippy").description("Lint the package"),
+        Suggestion::new("run")
+            .display("run, r")
+            .description("Run a binary or example"),
+        Suggestion::new("test")
+            .display("test, t")
+            .description("Run the tests"),
+    ];
+
+    let command: String = cliclack::input("Pick a command")
+        .autocomplete(commands)
+        .interact()?;
+
+    let crate_name: String = cliclack::input("Pick a crate")
+        .autocomplete_with(|query: &str| match query.len() {
+            0..=1 => Err("Type at least 2 characters".to_string()),
+            _ => Ok(vec![format!("{query}-core"), format!("{query}-derive")]),
+        })
+        .interact()?;
+
+    cliclack::outro(format!(
+        "Selected: {language}, cargo {command}, {crate_name}"
+    ))?;
 
     Ok(())
 }
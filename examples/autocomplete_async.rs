@@ -0,0 +1,37 @@
+use std::{thread, time::Duration};
+
+use cliclack::AsyncAutocomplete;
+use console::style;
+
+fn main() -> std::io::Result<()> {
+    ctrlc::set_handler(move || {}).expect("setting Ctrl-C handler");
+
+    cliclack::clear_screen()?;
+    cliclack::intro(style(" async autocomplete ").on_cyan().black())?;
+
+    // Pretends to be a slow source, e.g. a package index.
+    let packages = |query: &str| {
+        thread::sleep(Duration::from_millis(700));
+        let packages = [
+            "serde",
+            "serde_json",
+            "tokio",
+            "clap",
+            "console",
+            "cliclack",
+        ];
+        Ok(packages
+            .iter()
+            .filter(|package| !query.is_empty() && package.starts_with(query))
+            .map(|package| package.to_string())
+            .collect())
+    };
+
+    let package: String = cliclack::input("Add a dependency")
+        .autocompleter(AsyncAutocomplete::new(packages))
+        .interact()?;
+
+    cliclack::outro(format!("Added: {package}"))?;
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use cliclack::{intro, multiselect, outro, select};
+
+fn main() -> std::io::Result<()> {
+    intro("Grouped options")?;
+
+    let template = select("Pick a project template")
+        .item("empty", "Empty", "nothing but a README")
+        .disabled_item("compose", "Docker Compose", "requires docker")
+        .group(
+            "Frontend",
+            &[
+                ("react", "React", "with Vite"),
+                ("vue", "Vue", "with Vite"),
+                ("svelte", "Svelte", "SvelteKit"),
+            ],
+        )
+        .group(
+            "Backend",
+            &[
+                ("axum", "Axum", "Rust"),
+                ("express", "Express", "Node.js"),
+                ("fastapi", "FastAPI", "Python"),
+            ],
+        )
+        .filter_mode()
+        .max_rows(6)
+        .interact()?;
+
+    let tools = multiselect("Select additional tools (the header toggles a group)")
+        .group(
+            "Linters",
+            &[("eslint", "ESLint", ""), ("stylelint", "Stylelint", "")],
+        )
+        .group(
+            "Formatters",
+            &[("prettier", "Prettier", ""), ("biome", "Biome", "")],
+        )
+        .required(false)
+        .max_selected(3)
+        .filter_mode()
+        .interact()?;
+
+    outro(format!("You chose: {template}, then {}", tools.join(", ")))?;
+
+    Ok(())
+}
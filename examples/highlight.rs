@@ -0,0 +1,81 @@
+use cliclack::{input, intro, outro, Span};
+use console::Style;
+
+/// Returns the byte ranges of the whitespace-separated words.
+fn words(text: &str) -> Vec<(usize, &str)> {
+    let mut words = vec![];
+    let mut start = None;
+    for (ix, chr) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (start, chr.is_whitespace()) {
+            (None, false) => start = Some(ix),
+            (Some(from), true) => {
+                words.push((from, &text[from..ix]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+/// Highlights a shell command: the program, flags, and quoted strings.
+fn shell(text: &str) -> Vec<Span> {
+    words(text)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, (start, word))| {
+            let style = if i == 0 {
+                Style::new().green().bold()
+            } else if word.starts_with('-') {
+                Style::new().cyan()
+            } else if word.starts_with(['"', '\'']) {
+                Style::new().yellow()
+            } else {
+                return None;
+            };
+            Some(Span::new(start..start + word.len(), style))
+        })
+        .collect()
+}
+
+/// Highlights a cron expression underlining the invalid fields in red.
+fn cron(text: &str) -> Vec<Span> {
+    let valid = |field: &str| {
+        field
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '*' | '/' | ',' | '-'))
+    };
+
+    words(text)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (start, field))| {
+            let style = if i >= 5 || !valid(field) {
+                Style::new().red().underlined()
+            } else {
+                Style::new().magenta()
+            };
+            Span::new(start..start + field.len(), style)
+        })
+        .collect()
+}
+
+fn main() -> std::io::Result<()> {
+    intro("Highlighting")?;
+
+    let _: String = input("Command")
+        .placeholder("cargo build --release")
+        .highlighter(shell)
+        // Long commands are kept on a single row.
+        .horizontal_scroll()
+        .interact()?;
+
+    let _: String = input("Schedule (cron)")
+        .placeholder("*/5 * * * *")
+        .highlighter(cron)
+        .interact()?;
+
+    outro("Done")?;
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use console::style;
+
+fn main() -> std::io::Result<()> {
+    // Set a no-op Ctrl-C to make it behave as `Esc` (see the basic example for details).
+    ctrlc::set_handler(move || {}).expect("setting Ctrl-C handler");
+
+    cliclack::clear_screen()?;
+    cliclack::intro(style(" history ").on_cyan().black())?;
+
+    let host: String = cliclack::input("Host (Up/Down to recall, Ctrl-R to search)")
+        .placeholder("example.com")
+        .history("host")
+        .interact()?;
+
+    let branch: String = cliclack::input("Branch")
+        .default_input("main")
+        .history("branch")
+        .interact()?;
+
+    cliclack::outro(format!("Deploying {branch} to {host}"))?;
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use cliclack::InputMask;
+use console::style;
+
+fn main() -> std::io::Result<()> {
+    // Set a no-op Ctrl-C to make it behave as `Esc` (see the basic example for details).
+    ctrlc::set_handler(move || {}).expect("setting Ctrl-C handler");
+
+    cliclack::clear_screen()?;
+    cliclack::intro(style(" mask ").on_cyan().black())?;
+
+    let phone: String = cliclack::input("Phone number")
+        .mask("(999) 999-9999")
+        .interact()?;
+
+    let date: String = cliclack::input("Date of birth")
+        .mask(InputMask::date())
+        .interact()?;
+
+    let ip: String = cliclack::input("Server address")
+        .mask(InputMask::ipv4())
+        .interact()?;
+
+    cliclack::outro(format!("{phone}, {date}, {ip}"))?;
+
+    Ok(())
+}
@@ -7,6 +7,13 @@ fn main() -> std::io::Result<()> {
         .interact()?;
     note("Your input is:", res)?;
 
+    let res: String = input("With line numbers")
+        .placeholder("Type here...")
+        .multiline()
+        .line_numbers()
+        .interact()?;
+    note("Your input is:", res)?;
+
     let res: usize = input("Only number:")
         .placeholder("Type here...")
         .multiline()
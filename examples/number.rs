@@ -0,0 +1,29 @@
+use console::style;
+
+fn main() -> std::io::Result<()> {
+    // Set a no-op Ctrl-C to make it behave as `Esc` (see the basic example for details).
+    ctrlc::set_handler(move || {}).expect("setting Ctrl-C handler");
+
+    cliclack::clear_screen()?;
+    cliclack::intro(style(" number ").on_cyan().black())?;
+
+    let port: u16 = cliclack::number("Port")
+        .min(1)
+        .max(65535)
+        .default_value(8080)
+        .interact()?;
+
+    let replicas: u32 = cliclack::number("Replicas").min(1).max(10).interact()?;
+
+    let timeout: f64 = cliclack::number("Timeout, seconds")
+        .min(0.0)
+        .step(0.5)
+        .default_value(2.5)
+        .interact()?;
+
+    cliclack::outro(format!(
+        "{replicas} replica(s) on port {port} with {timeout}s timeout"
+    ))?;
+
+    Ok(())
+}
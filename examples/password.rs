@@ -0,0 +1,19 @@
+use cliclack::{intro, outro, password, Strength};
+
+fn main() -> std::io::Result<()> {
+    intro("Create an account")?;
+
+    let _password = password("Choose a password")
+        .confirm("Repeat the password")
+        .strength(Strength::estimate)
+        .allow_reveal()
+        .validate(|password: &String| match Strength::estimate(password) {
+            Strength::VeryWeak | Strength::Weak => Err("The password is too weak"),
+            _ => Ok(()),
+        })
+        .interact()?;
+
+    outro("Account created")?;
+
+    Ok(())
+}
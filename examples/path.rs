@@ -0,0 +1,30 @@
+use console::style;
+
+fn main() -> std::io::Result<()> {
+    // Set a no-op Ctrl-C to make it behave as `Esc` (see the basic example for details).
+    ctrlc::set_handler(move || {}).expect("setting Ctrl-C handler");
+
+    cliclack::clear_screen()?;
+    cliclack::intro(style(" path ").on_cyan().black())?;
+
+    let manifest = cliclack::path("Which manifest to use?")
+        .placeholder("Cargo.toml")
+        .extensions(["toml"])
+        .file()
+        .must_exist()
+        .interact()?;
+
+    let target = cliclack::path("Where should we create your project?")
+        .placeholder("./my-app")
+        .directory()
+        .must_not_exist()
+        .interact()?;
+
+    cliclack::outro(format!(
+        "Using {} to create {}",
+        manifest.display(),
+        target.display()
+    ))?;
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use cliclack::Validator;
 use console::style;
 
 #[allow(clippy::ptr_arg)]
@@ -21,6 +22,31 @@ fn main() -> std::io::Result<()> {
         .validate_on_enter(check_username_is_available)
         .interact()?;
 
+    let _project: String = cliclack::input("Project name (a free directory name)")
+        .validate_in_background(|name: &String| {
+            // Simulate a slow check.
+            std::thread::sleep(std::time::Duration::from_secs(1));
+            match std::path::Path::new(name).exists() {
+                true => Err("The directory already exists"),
+                false => Ok(()),
+            }
+        })
+        .interact()?;
+
+    let _subject: String = cliclack::input("Commit subject")
+        .min_length(10)
+        .max_length(50)
+        .interact()?;
+
+    let _email: String = cliclack::input("Email")
+        .validate_interactively(Validator::email())
+        .interact()?;
+
+    let _port: u16 = cliclack::input("Port")
+        .default_input("8080")
+        .validate(Validator::range(1024..=65535).message("Expected an unprivileged port"))
+        .interact()?;
+
     let _password = cliclack::password("Provide a password")
         .mask('▪')
         .validate_interactively(|x: &String| {
@@ -30,6 +56,7 @@ fn main() -> std::io::Result<()> {
                 Ok(())
             }
         })
+        .validate(Validator::regex(r".*\d.*").message("password should contain a digit"))
         .interact()?;
 
     cliclack::note("User created", format!("{username}\n▪▪▪▪▪\n"))?;
@@ -1,33 +1,385 @@
-use std::path::PathBuf;
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
 
-use crate::filter::filter_strings;
+use crate::filter::{filter_items, filter_strings, LabeledItem};
 
+/// The default delay after the last keystroke before querying a background provider.
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(150);
+const DEFAULT_CACHE_SIZE: usize = 100;
+
+/// An autocompletion suggestion: a value inserted into the input, and
+/// optionally a different text to display and a description.
+///
+/// ```
+/// use cliclack::Suggestion;
+///
+/// let suggestion = Suggestion::new("--force")
+///     .display("-f, --force")
+///     .description("Overwrite existing files");
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Suggestion {
+    value: String,
+    display: Option<String>,
+    description: Option<String>,
+}
+
+impl Suggestion {
+    /// Creates a suggestion inserting a given value.
+    pub fn new(value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            display: None,
+            description: None,
+        }
+    }
+
+    /// Sets the text displayed in the list instead of the value.
+    pub fn display(mut self, display: impl Into<String>) -> Self {
+        self.display = Some(display.into());
+        self
+    }
+
+    /// Sets the description shown dimmed next to the suggestion.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Returns the value inserted into the input.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the displayed text (the value if not set).
+    pub fn label(&self) -> &str {
+        self.display.as_deref().unwrap_or(&self.value)
+    }
+
+    /// Returns the description (empty if not set).
+    pub fn hint(&self) -> &str {
+        self.description.as_deref().unwrap_or_default()
+    }
+}
+
+impl From<String> for Suggestion {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for Suggestion {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl LabeledItem for Suggestion {
+    fn label(&self) -> &str {
+        self.label()
+    }
+}
+
+/// The result of an autocompletion query: a list of suggested values or an error message.
+///
+/// The error message is shown in the footer of the prompt.
 pub type AutocompleteResult = Result<Vec<String>, String>;
 
+/// The result of an autocompletion query with [`Suggestion`]s: values with
+/// display texts and descriptions.
+pub type SuggestionResult = Result<Vec<Suggestion>, String>;
+
+/// Provides suggestions for the [`Input`](crate::Input) prompt.
+///
+/// Implemented for a static list of suggestions (`Vec<String>`, `Vec<Suggestion>`)
+/// and for closures `Fn(&str) -> Result<Vec<S>, String>` where `S` is either
+/// a [`Suggestion`] or anything convertible into it, like `String`.
 pub trait Autocomplete: Send {
+    /// Returns the suggested values matching the current input.
     fn get_suggestions(&mut self, input: &str) -> AutocompleteResult;
-    fn get_completion(&mut self, input: &str, highlighted: Option<String>) -> Option<String>;
+
+    /// Returns the suggestions matching the current input with display texts
+    /// and descriptions. The prompt shows these ones.
+    ///
+    /// By default, it's the [`get_suggestions()`](Autocomplete::get_suggestions) values.
+    fn get_rich_suggestions(&mut self, input: &str) -> SuggestionResult {
+        self.get_suggestions(input)
+            .map(|values| values.into_iter().map(Suggestion::from).collect())
+    }
+
+    /// Returns the completion of the current input given the highlighted suggestion.
+    ///
+    /// The part of the completion after the input is shown inline as a dimmed
+    /// hint, `Right`/`End` or `Tab` accept it.
+    ///
+    /// By default, it's the highlighted suggestion or the first suggestion
+    /// starting with the input.
+    fn get_completion(&mut self, input: &str, highlighted: Option<String>) -> Option<String> {
+        highlighted.or_else(|| {
+            let suggestions = self.get_suggestions(input).ok()?;
+            suggestions
+                .into_iter()
+                .find(|value| value.starts_with(input))
+        })
+    }
+
+    /// Whether the suggestions for the latest input are still being loaded,
+    /// see [`AsyncAutocomplete`]. `false` by default.
+    fn is_loading(&self) -> bool {
+        false
+    }
+}
+
+/// Returns the values of the suggestions.
+fn values(result: SuggestionResult) -> AutocompleteResult {
+    result.map(|suggestions| {
+        suggestions
+            .into_iter()
+            .map(|suggestion| suggestion.value)
+            .collect()
+    })
 }
 
 impl Autocomplete for Vec<String> {
     fn get_suggestions(&mut self, input: &str) -> AutocompleteResult {
         Ok(filter_strings(input, self))
     }
+}
+
+impl Autocomplete for Vec<Suggestion> {
+    fn get_suggestions(&mut self, input: &str) -> AutocompleteResult {
+        values(self.get_rich_suggestions(input))
+    }
 
-    fn get_completion(&mut self, _input: &str, highlighted: Option<String>) -> Option<String> {
-        highlighted
+    fn get_rich_suggestions(&mut self, input: &str) -> SuggestionResult {
+        Ok(filter_items(input, self))
     }
 }
 
-impl<F> Autocomplete for F
+impl<F, S> Autocomplete for F
 where
-    F: Fn(&str) -> AutocompleteResult + Send,
+    F: Fn(&str) -> Result<Vec<S>, String> + Send,
+    S: Into<Suggestion>,
 {
     fn get_suggestions(&mut self, input: &str) -> AutocompleteResult {
-        self(input)
+        values(self.get_rich_suggestions(input))
     }
 
-    fn get_completion(&mut self, _input: &str, highlighted: Option<String>) -> Option<String> {
-        highlighted
+    fn get_rich_suggestions(&mut self, input: &str) -> SuggestionResult {
+        self(input).map(|suggestions| suggestions.into_iter().map(Into::into).collect())
     }
-}
\ No newline at end of file
+}
+
+/// The channels to the background thread of [`AsyncAutocomplete`].
+struct Worker {
+    queries: Sender<String>,
+    results: Receiver<(String, SuggestionResult)>,
+}
+
+impl Worker {
+    fn spawn(mut provider: Box<dyn Autocomplete>, debounce: Duration) -> Self {
+        let (queries, received) = mpsc::channel::<String>();
+        let (results, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            while let Ok(mut query) = received.recv() {
+                // Wait for the input to settle skipping the stale queries.
+                loop {
+                    match received.recv_timeout(debounce) {
+                        Ok(newer) => query = newer,
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+
+                let result = provider.get_rich_suggestions(&query);
+                if results.send((query, result)).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Self {
+            queries,
+            results: receiver,
+        }
+    }
+}
+
+/// Runs a slow autocompletion provider on a background thread, so that
+/// typing doesn't freeze while suggestions are loaded (e.g. from git refs,
+/// a package index, or a local service).
+///
+/// * The provider is queried once the input settles down (debouncing),
+///   the intermediate (stale) queries are skipped.
+/// * A loading indicator is shown in place of the suggestions meanwhile.
+/// * The results are cached per query, up to 100 latest queries by default.
+/// * A query which is being loaded can't be interrupted: the thread exits
+///   once the provider returns after the prompt is finished.
+///
+/// ```
+/// use std::time::Duration;
+/// use cliclack::{AsyncAutocomplete, Input};
+///
+/// # fn test() -> std::io::Result<()> {
+/// let branches = |query: &str| {
+///     // E.g. run `git branch --list` here.
+///     Ok(vec![format!("feature/{query}")])
+/// };
+///
+/// let branch: String = Input::new("Branch")
+///     .autocompleter(AsyncAutocomplete::new(branches).debounce(Duration::from_millis(300)))
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
+pub struct AsyncAutocomplete {
+    provider: Option<Box<dyn Autocomplete>>,
+    debounce: Duration,
+    worker: Option<Worker>,
+    cache: HashMap<String, SuggestionResult>,
+    /// The cached queries from the oldest to the newest one.
+    cached: VecDeque<String>,
+    cache_size: usize,
+    /// The latest input.
+    query: Option<String>,
+    /// The latest query sent to the background thread.
+    requested: Option<String>,
+}
+
+impl AsyncAutocomplete {
+    /// Wraps a provider to be run on a background thread.
+    pub fn new(provider: impl Autocomplete + 'static) -> Self {
+        Self {
+            provider: Some(Box::new(provider)),
+            debounce: DEFAULT_DEBOUNCE,
+            worker: None,
+            cache: HashMap::new(),
+            cached: VecDeque::new(),
+            cache_size: DEFAULT_CACHE_SIZE,
+            query: None,
+            requested: None,
+        }
+    }
+
+    /// Sets the delay after the last keystroke before querying the provider.
+    /// Default: 150 ms.
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Sets the maximum number of cached queries, the oldest ones are evicted.
+    /// Default: 100, the minimum is 1 (the current query's results).
+    pub fn cache_size(mut self, size: usize) -> Self {
+        self.cache_size = size.max(1);
+        self
+    }
+
+    /// Caches the result evicting the oldest queries over the limit.
+    fn cache(&mut self, query: String, result: SuggestionResult) {
+        if self.cache.insert(query.clone(), result).is_none() {
+            self.cached.push_back(query);
+        }
+        while self.cached.len() > self.cache_size {
+            if let Some(oldest) = self.cached.pop_front() {
+                self.cache.remove(&oldest);
+            }
+        }
+    }
+
+    /// Sends the query to the background thread spawning it if needed.
+    fn request(&mut self, query: &str) {
+        let debounce = self.debounce;
+        let provider = &mut self.provider;
+        let worker = self.worker.get_or_insert_with(|| {
+            let provider = provider
+                .take()
+                .expect("provider is moved to the worker once");
+            Worker::spawn(provider, debounce)
+        });
+
+        if worker.queries.send(query.to_string()).is_ok() {
+            self.requested = Some(query.to_string());
+        }
+    }
+}
+
+impl Autocomplete for AsyncAutocomplete {
+    fn get_suggestions(&mut self, input: &str) -> AutocompleteResult {
+        values(self.get_rich_suggestions(input))
+    }
+
+    fn get_rich_suggestions(&mut self, input: &str) -> SuggestionResult {
+        let results: Vec<_> = match &self.worker {
+            Some(worker) => worker.results.try_iter().collect(),
+            None => vec![],
+        };
+        for (query, result) in results {
+            self.cache(query, result);
+        }
+
+        self.query = Some(input.to_string());
+        if let Some(result) = self.cache.get(input) {
+            return result.clone();
+        }
+        if self.requested.as_deref() != Some(input) {
+            self.request(input);
+        }
+        Ok(vec![])
+    }
+
+    fn is_loading(&self) -> bool {
+        self.query
+            .as_ref()
+            .is_some_and(|query| !self.cache.contains_key(query))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn background_loading() {
+        let provider = |query: &str| Ok(vec![format!("{query}!")]);
+        let suggestion = |value: &str| Ok(vec![value.to_string()]);
+        let mut completer = AsyncAutocomplete::new(provider).debounce(Duration::ZERO);
+
+        assert_eq!(completer.get_suggestions("a"), Ok(vec![]));
+        assert!(completer.is_loading());
+
+        let started = Instant::now();
+        while completer.is_loading() && started.elapsed() < Duration::from_secs(5) {
+            thread::sleep(Duration::from_millis(10));
+            completer.get_suggestions("a").unwrap();
+        }
+        assert_eq!(completer.get_suggestions("a"), suggestion("a!"));
+
+        // The cached result is returned without waiting.
+        completer.get_suggestions("ab").unwrap();
+        assert_eq!(completer.get_suggestions("a"), suggestion("a!"));
+        assert!(!completer.is_loading());
+    }
+
+    #[test]
+    fn cache_size() {
+        let mut completer = AsyncAutocomplete::new(|_: &str| Ok(vec![String::new()])).cache_size(2);
+        completer.cache("a".into(), Ok(vec![]));
+        completer.cache("b".into(), Ok(vec![]));
+        completer.cache("a".into(), Ok(vec![]));
+        completer.cache("c".into(), Ok(vec![]));
+        assert!(!completer.cache.contains_key("a"));
+        assert_eq!(completer.cached, ["b", "c"]);
+
+        // The results of the last query are kept to be shown.
+        let mut completer = AsyncAutocomplete::new(|_: &str| Ok(vec![String::new()])).cache_size(0);
+        completer.cache("a".into(), Ok(vec![]));
+        assert!(completer.cache.contains_key("a"));
+    }
+}
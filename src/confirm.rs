@@ -81,4 +81,9 @@ impl PromptInteraction<bool> for Confirm {
 
         line1 + &line2 + &line3
     }
+
+    fn transcript(&self) -> Option<(String, String)> {
+        let answer = if self.input { "Yes" } else { "No" };
+        Some((self.prompt.clone(), answer.to_string()))
+    }
 }
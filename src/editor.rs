@@ -0,0 +1,121 @@
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Returns the command of the user's editor: `$VISUAL`, `$EDITOR`,
+/// or a platform default.
+pub(crate) fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(env::var_os)
+        .map(|value| value.to_string_lossy().trim().to_string())
+        .find(|command| !command.is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "notepad" } else { "vi" }.to_string())
+}
+
+/// Returns the process running the editor command on a file.
+///
+/// The command is interpreted by the shell, so it may contain arguments
+/// and quoted paths, e.g. `code --wait` or `"/Applications/Sublime Text.app/…/subl" -w`.
+#[cfg(unix)]
+fn editor_process(command: &str, path: &Path) -> Command {
+    let mut process = Command::new("sh");
+    process
+        .arg("-c")
+        .arg(format!("{command} \"$1\""))
+        .arg("--")
+        .arg(path);
+    process
+}
+
+/// Returns the process running the editor command on a file.
+///
+/// The command may contain arguments and double-quoted paths,
+/// e.g. `"C:\Program Files\Notepad++\notepad++.exe" -multiInst`.
+#[cfg(not(unix))]
+fn editor_process(command: &str, path: &Path) -> Command {
+    let mut args = split_args(command).into_iter();
+    let mut process = Command::new(args.next().unwrap_or_default());
+    process.args(args).arg(path);
+    process
+}
+
+/// Splits the command into arguments by whitespace except in double quotes.
+#[cfg(not(unix))]
+fn split_args(command: &str) -> Vec<String> {
+    let mut args = vec![];
+    let mut arg: Option<String> = None;
+    let mut quoted = false;
+    for chr in command.chars() {
+        match chr {
+            '"' => {
+                quoted = !quoted;
+                arg.get_or_insert_with(String::new);
+            }
+            chr if chr.is_whitespace() && !quoted => args.extend(arg.take()),
+            chr => arg.get_or_insert_with(String::new).push(chr),
+        }
+    }
+    args.extend(arg);
+    args
+}
+
+/// Runs the editor command on a file waiting for it to exit.
+fn run(command: &str, path: &Path) -> io::Result<()> {
+    let status = editor_process(command, path).status()?;
+
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "`{command}` exited with {status}"
+        )));
+    }
+    Ok(())
+}
+
+/// Opens the text in the external editor (see [`editor_command`]) via
+/// a temporary file, and returns the edited text.
+pub(crate) fn edit(text: &str, command: &str) -> io::Result<String> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.subsec_nanos());
+    let path = env::temp_dir().join(format!("cliclack-{}-{nanos}.txt", std::process::id()));
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)?;
+    file.write_all(text.as_bytes())?;
+    drop(file);
+
+    let edited = run(command, &path).and_then(|_| fs::read_to_string(&path));
+    fs::remove_file(&path).ok();
+    let mut edited = edited?;
+
+    // Editors usually add a trailing newline.
+    if !text.ends_with('\n') && edited.ends_with('\n') {
+        edited.pop();
+        if edited.ends_with('\r') {
+            edited.pop();
+        }
+    }
+    Ok(edited)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_with_command() {
+        // The "editor" overwrites the file with another one (a quoted path with spaces).
+        let edited = env::temp_dir().join(format!("cliclack edited {}.txt", std::process::id()));
+        fs::write(&edited, "goodbye\nworld\n").unwrap();
+        let result = edit("hello\nworld", &format!("cp \"{}\"", edited.display()));
+        fs::remove_file(&edited).ok();
+
+        assert_eq!(result.unwrap(), "goodbye\nworld");
+    }
+}
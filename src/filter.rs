@@ -6,10 +6,27 @@ use crate::prompt::{cursor::StringCursor, interaction::State};
 
 pub(crate) trait LabeledItem {
     fn label(&self) -> &str;
+
+    /// The index of the group the item belongs to, the filtered items
+    /// are kept together by groups.
+    fn group(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl LabeledItem for String {
+    fn label(&self) -> &str {
+        self
+    }
 }
 
 /// Filters a list of strings using fuzzy matching (Jaro-Winkler).
 pub fn filter_strings(input: &str, items: &[String]) -> Vec<String> {
+    filter_items(input, items)
+}
+
+/// Filters a list of items by their labels using fuzzy matching (Jaro-Winkler).
+pub(crate) fn filter_items<I: LabeledItem + Clone>(input: &str, items: &[I]) -> Vec<I> {
     if input.is_empty() {
         return vec![];
     }
@@ -20,7 +37,7 @@ pub fn filter_strings(input: &str, items: &[String]) -> Vec<String> {
     let mut filtered_and_scored: Vec<_> = items
         .iter()
         .map(|item| {
-            let label = item.to_lowercase();
+            let label = item.label().to_lowercase();
             let similarity = strsim::jaro_winkler(&label, &input_lower);
             let bonus = filter_words.iter().all(|word| label.contains(word)) as usize as f64;
             (similarity + bonus, item.clone())
@@ -36,6 +53,23 @@ pub fn filter_strings(input: &str, items: &[String]) -> Vec<String> {
         .collect()
 }
 
+/// Returns the positions of the label characters matching the input
+/// characters in order (case-insensitive), to highlight them.
+pub(crate) fn matched_chars(input: &str, label: &str) -> Vec<usize> {
+    let mut matched = vec![];
+    let mut label_chars = label.chars().enumerate();
+
+    for chr in input.chars().filter(|chr| !chr.is_whitespace()) {
+        let chr = chr.to_lowercase().collect::<String>();
+        let mut rest = label_chars.clone();
+        if let Some((ix, _)) = rest.find(|(_, c)| c.to_lowercase().collect::<String>() == chr) {
+            matched.push(ix);
+            label_chars = rest;
+        }
+    }
+    matched
+}
+
 /// The list of items gathered (filtered) by interactive input using
 /// `FilteredView::on` event in a selection prompt.
 pub(crate) struct FilteredView<I: LabeledItem> {
@@ -120,6 +154,8 @@ impl<I: LabeledItem + Clone> FilteredView<I> {
                     .collect();
 
                 filtered_and_scored_items.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+                // Keep the groups together (the sort is stable).
+                filtered_and_scored_items.sort_by_key(|(_, item)| item.borrow().group());
 
                 self.items = filtered_and_scored_items
                     .into_iter()
@@ -0,0 +1,62 @@
+use std::ops::Range;
+
+use console::Style;
+
+/// A styled part of the input text returned by the highlighter of the
+/// [`Input`](crate::Input) prompt (see [`Input::highlighter`](crate::Input::highlighter)).
+///
+/// The range is in bytes of the text, like the ranges of [`str::find`]
+/// or [`str::char_indices`].
+///
+/// ```
+/// use cliclack::Span;
+/// use console::Style;
+///
+/// let text = "git commit";
+/// let command = Span::new(0..text.find(' ').unwrap(), Style::new().green());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Span {
+    range: Range<usize>,
+    style: Style,
+}
+
+impl Span {
+    /// Creates a span styling a given range of bytes.
+    pub fn new(range: Range<usize>, style: Style) -> Self {
+        Self { range, style }
+    }
+}
+
+/// Converts the byte ranges of the spans into the character ranges of a given
+/// text, snapping them to the character boundaries.
+pub(crate) fn char_ranges(text: &str, spans: Vec<Span>) -> Vec<(Range<usize>, Style)> {
+    let char_index = |byte: usize| text.char_indices().take_while(|(ix, _)| *ix < byte).count();
+
+    spans
+        .into_iter()
+        .map(|span| {
+            let start = char_index(span.range.start);
+            let end = char_index(span.range.end).max(start);
+            (start..end, span.style)
+        })
+        .filter(|(range, _)| !range.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_ranges() {
+        let spans = vec![
+            Span::new(0..3, Style::new().red()),
+            Span::new(4..9, Style::new().bold()),
+            Span::new(9..100, Style::new()),
+        ];
+        let ranges = char_ranges("añb {x}", spans);
+        let ranges: Vec<_> = ranges.into_iter().map(|(range, _)| range).collect();
+        assert_eq!(ranges, vec![0..2, 3..7]);
+    }
+}
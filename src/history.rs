@@ -0,0 +1,303 @@
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// The maximum number of entries kept per history key.
+const MAX_ENTRIES: usize = 500;
+
+/// Returns the default history file location: `$XDG_DATA_HOME/cliclack/history`
+/// (`~/.local/share/cliclack/history` as a fallback), or
+/// `%APPDATA%\cliclack\history` on Windows.
+fn default_path() -> Option<PathBuf> {
+    let data_dir = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))
+    };
+
+    data_dir.map(|dir| dir.join("cliclack").join("history"))
+}
+
+/// Escapes a key or a value to be stored in a single line of the history file.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for chr in value.chars() {
+        match chr {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(chr),
+        }
+    }
+    escaped
+}
+
+/// Reverts [`escape`].
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(chr) = chars.next() {
+        if chr != '\\' {
+            unescaped.push(chr);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Rewrites the history file once it has more than [`MAX_ENTRIES`] lines
+/// of a given (escaped) key, keeping the last unique entries of the key.
+/// The lines of the other keys are kept as is.
+fn compact(path: &Path, key: &str) -> io::Result<()> {
+    let content = fs::read_to_string(path)?;
+    let is_key = |line: &&str| line.split_once('\t').is_some_and(|(k, _)| k == key);
+    if content.lines().filter(is_key).count() <= MAX_ENTRIES {
+        return Ok(());
+    }
+
+    let mut entries: Vec<&str> = vec![];
+    for line in content.lines().filter(is_key) {
+        entries.retain(|entry| *entry != line);
+        entries.push(line);
+    }
+    let start = entries.len().saturating_sub(MAX_ENTRIES);
+
+    let mut compacted = String::with_capacity(content.len());
+    for line in content.lines().filter(|line| !is_key(line)) {
+        compacted.push_str(line);
+        compacted.push('\n');
+    }
+    for line in &entries[start..] {
+        compacted.push_str(line);
+        compacted.push('\n');
+    }
+
+    // Replace the file at once, so that a failure doesn't lose the history.
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, compacted)?;
+    fs::rename(&temp, path)
+}
+
+/// Submitted values of the [`Input`](crate::Input) prompt persisted per key.
+///
+/// The history file is shared between keys: every line is a `key<TAB>value`
+/// pair, new values are appended to the end. Once a key has more than
+/// [`MAX_ENTRIES`] lines, the file is rewritten with its last entries.
+pub(crate) struct History {
+    key: String,
+    path: Option<PathBuf>,
+    entries: Vec<String>,
+    /// The position of the recalled entry, `None` while editing a new value.
+    index: Option<usize>,
+    /// The value being edited before the history navigation started.
+    draft: String,
+}
+
+impl History {
+    /// Creates a history for a given key stored in the default location.
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            path: default_path(),
+            entries: vec![],
+            index: None,
+            draft: String::new(),
+        }
+    }
+
+    /// Overrides the location of the history file.
+    pub fn set_path(&mut self, path: PathBuf) {
+        self.path = Some(path);
+    }
+
+    /// Reads the entries of the key from the history file.
+    ///
+    /// A missing or unreadable file results in an empty history.
+    pub fn load(&mut self) {
+        let Some(content) = self.path.as_ref().and_then(|p| fs::read_to_string(p).ok()) else {
+            return;
+        };
+
+        let key = escape(&self.key);
+        self.entries.clear();
+        for line in content.lines() {
+            if let Some((k, value)) = line.split_once('\t') {
+                if k == key {
+                    self.remember(unescape(value));
+                }
+            }
+        }
+    }
+
+    /// Adds a submitted value to the history and appends it to the history file.
+    pub fn save(&mut self, value: &str) -> io::Result<()> {
+        self.reset();
+        if value.is_empty() {
+            return Ok(());
+        }
+
+        self.remember(value.to_string());
+
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}\t{}", escape(&self.key), escape(value))?;
+        drop(file);
+        compact(path, &escape(&self.key))
+    }
+
+    /// Adds the value to the end moving a duplicate entry if any.
+    fn remember(&mut self, value: String) {
+        self.entries.retain(|entry| *entry != value);
+        self.entries.push(value);
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+    }
+
+    /// Returns the previous (older) entry, saving the current value as a draft
+    /// if the navigation has just started.
+    pub fn previous(&mut self, current: &str) -> Option<&str> {
+        let index = match self.index {
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(index) => index.saturating_sub(1),
+        };
+        self.index = Some(index);
+        Some(&self.entries[index])
+    }
+
+    /// Returns the next (newer) entry, or the draft when the navigation is
+    /// over the newest entry.
+    pub fn next(&mut self) -> Option<&str> {
+        let index = self.index?;
+        if index + 1 < self.entries.len() {
+            self.index = Some(index + 1);
+            Some(&self.entries[index + 1])
+        } else {
+            self.index = None;
+            Some(&self.draft)
+        }
+    }
+
+    /// Stops the navigation: the recalled value becomes a regular input.
+    pub fn reset(&mut self) {
+        self.index = None;
+    }
+
+    /// Returns the entry at a given position.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// Searches the entries backwards for the one containing the query,
+    /// starting before the given position (or from the newest entry).
+    ///
+    /// Returns the position of the found entry and the entry itself.
+    pub fn search(&self, query: &str, before: Option<usize>) -> Option<(usize, &str)> {
+        let end = before.unwrap_or(self.entries.len()).min(self.entries.len());
+        self.entries[..end]
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, entry)| entry.contains(query))
+            .map(|(ix, entry)| (ix, entry.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escaping() {
+        let value = "a\\b\tc\nd";
+        assert_eq!(escape(value), "a\\\\b\\tc\\nd");
+        assert_eq!(unescape(&escape(value)), value);
+    }
+
+    #[test]
+    fn navigation() {
+        let mut history = History::new("test");
+        history.path = None;
+        for value in ["one", "two", "one", "three"] {
+            history.save(value).unwrap();
+        }
+        assert_eq!(history.entries, vec!["two", "one", "three"]);
+
+        assert_eq!(history.previous("draft"), Some("three"));
+        assert_eq!(history.previous("three"), Some("one"));
+        assert_eq!(history.previous("one"), Some("two"));
+        assert_eq!(history.previous("two"), Some("two"));
+        assert_eq!(history.next(), Some("one"));
+        assert_eq!(history.next(), Some("three"));
+        assert_eq!(history.next(), Some("draft"));
+        assert_eq!(history.next(), None);
+
+        assert_eq!(history.search("o", None), Some((1, "one")));
+        assert_eq!(history.search("o", Some(1)), Some((0, "two")));
+        assert_eq!(history.search("o", Some(0)), None);
+    }
+
+    #[test]
+    fn persistence() {
+        let path = env::temp_dir().join(format!("cliclack-history-{}", std::process::id()));
+        let mut history = History::new("host");
+        history.set_path(path.clone());
+        history.save("example.com").unwrap();
+        history.save("multi\nline").unwrap();
+
+        let mut other = History::new("branch");
+        other.set_path(path.clone());
+        other.save("main").unwrap();
+
+        let mut loaded = History::new("host");
+        loaded.set_path(path.clone());
+        loaded.load();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(loaded.entries, vec!["example.com", "multi\nline"]);
+    }
+
+    #[test]
+    fn file_limit() {
+        let path = env::temp_dir().join(format!("cliclack-history-limit-{}", std::process::id()));
+        let mut other = History::new("branch");
+        other.set_path(path.clone());
+        other.save("main").unwrap();
+
+        let mut history = History::new("host");
+        history.set_path(path.clone());
+        for ix in 0..MAX_ENTRIES + 10 {
+            history
+                .save(&format!("host-{}", ix % (MAX_ENTRIES + 5)))
+                .unwrap();
+        }
+        let content = fs::read_to_string(&path).unwrap();
+        let mut loaded = History::new("host");
+        loaded.set_path(path.clone());
+        loaded.load();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(content.lines().count(), MAX_ENTRIES + 1);
+        assert!(content.starts_with("branch\tmain\n"));
+        assert_eq!(loaded.entries, history.entries);
+    }
+}
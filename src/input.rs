@@ -1,19 +1,41 @@
 use std::io;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 use std::{fmt::Display, str::FromStr};
 
-use console::Key;
+use console::{measure_text_width, Key};
 
 use crate::{
-    autocomplete::{Autocomplete},
+    autocomplete::{Autocomplete, Suggestion},
+    editor,
+    filter::matched_chars,
+    highlight::{char_ranges, Span},
+    history::History,
+    mask::InputMask,
+    output,
     prompt::{
         cursor::StringCursor,
-        interaction::{Event, PromptInteraction, State},
+        interaction::{wrap, Event, PromptInteraction, State},
     },
-    theme::THEME,
+    theme::{Theme, ThemeState, THEME},
     validate::Validate,
+    validator::Validator,
+    view::ListView,
 };
 
+/// The default maximum number of visible autocompletion suggestions.
+const DEFAULT_MAX_SUGGESTIONS: usize = 10;
+
+/// The refresh interval of the prompt while autocompletion suggestions are loading.
+const LOADING_REFRESH_INTERVAL: Duration = Duration::from_millis(80);
+
 type ValidationCallback = Box<dyn Fn(&String) -> Result<(), String>>;
+type BackgroundValidationCallback = Arc<dyn Fn(&String) -> Result<(), String> + Send + Sync>;
+type ParseCallback<'a, T> = Box<dyn Fn(&str) -> Result<T, String> + 'a>;
+type HighlightCallback = Box<dyn Fn(&str) -> Vec<Span>>;
 
 #[derive(Default, PartialEq)]
 enum Multiline {
@@ -23,6 +45,14 @@ enum Multiline {
     Editing,
 }
 
+/// The state of the reverse search in the history (`Ctrl-R`).
+struct HistorySearch {
+    /// The input before the search started.
+    draft: String,
+    /// The position of the found history entry.
+    found: Option<usize>,
+}
+
 /// A prompt that accepts a text input: either single-line or multiline.
 ///
 /// # Example
@@ -64,10 +94,41 @@ pub struct Input {
     multiline: Multiline,
     validate_on_enter: Option<ValidationCallback>,
     validate_interactively: Option<ValidationCallback>,
+    validate_in_background: Option<BackgroundValidationCallback>,
+    /// The result of the running background validation.
+    validation: Option<Receiver<Result<(), String>>>,
+    highlighter: Option<HighlightCallback>,
     autocompleter: Option<Box<dyn Autocomplete>>,
     autocompletion_index: Option<usize>,
     autocompletion_query: String,
     autocomplete_on_enter: bool,
+    /// The rest of the inline completion shown after the cursor.
+    completion: Option<String>,
+    /// The animation frame of the suggestions loading indicator.
+    loading_frame: usize,
+    /// The error returned by the autocompletion provider.
+    autocomplete_error: Option<String>,
+    /// The visible part of the suggestion list.
+    suggestions_view: ListView,
+    /// The last rendered frame to redraw it after the external editor.
+    last_frame: String,
+    /// Whether `Ctrl-X` has been pressed starting a key chord.
+    ctrl_x: bool,
+    /// Whether to show line numbers in the multiline input.
+    line_numbers: bool,
+    /// The visible rows of the multiline input.
+    editor_view: ListView,
+    /// The minimum number of characters.
+    min_length: Option<usize>,
+    /// The maximum number of characters.
+    max_length: Option<usize>,
+    /// Whether to scroll a long single-line input horizontally.
+    horizontal_scroll: bool,
+    /// The first character shown in the horizontally scrolled input.
+    window_start: usize,
+    history: Option<History>,
+    history_file: Option<PathBuf>,
+    history_search: Option<HistorySearch>,
 }
 
 impl Input {
@@ -76,6 +137,10 @@ impl Input {
         Self {
             prompt: prompt.to_string(),
             input_required: true,
+            suggestions_view: ListView {
+                height: DEFAULT_MAX_SUGGESTIONS,
+                start: 0,
+            },
             ..Default::default()
         }
     }
@@ -107,11 +172,57 @@ impl Input {
     ///
     /// 1. Press `Esc` to review and submit.
     /// 2. Start typing to get back into the editing mode.
+    /// 3. Press `Ctrl-X Ctrl-E` to edit the text in the external editor
+    ///    (`$VISUAL` or `$EDITOR`).
+    ///
+    /// Long lines are wrapped at the terminal width (`Up` and `Down` move
+    /// through the wrapped lines), and the text taller than the terminal
+    /// is scrolled following the cursor.
     pub fn multiline(mut self) -> Self {
         self.multiline = Multiline::Editing;
         self
     }
 
+    /// Shows line numbers in the left gutter of the multiline input
+    /// (see [`Input::multiline`]).
+    pub fn line_numbers(mut self) -> Self {
+        self.line_numbers = true;
+        self
+    }
+
+    /// Limits the input to a given number of characters: typing beyond
+    /// the limit is blocked, and a live counter (like `12/50`) is shown
+    /// in the footer.
+    ///
+    /// Characters are Unicode scalar values (`char`s) as in
+    /// [`Validator::max_length`], so e.g. an accented letter typed as
+    /// a combining sequence counts as two.
+    pub fn max_length(mut self, max: usize) -> Self {
+        self.max_length = Some(max);
+        self.input.set_max_len(Some(max));
+        self
+    }
+
+    /// Requires at least a given number of characters on submit (unless
+    /// the input is empty and not [`required`](Input::required)), a live
+    /// counter is shown in the footer.
+    ///
+    /// The check is [`Validator::min_length`], so characters are counted
+    /// the same way as in [`Input::max_length`].
+    pub fn min_length(mut self, min: usize) -> Self {
+        self.min_length = Some(min);
+        self
+    }
+
+    /// Keeps a long single-line input on a single row of the terminal,
+    /// scrolling it horizontally to follow the cursor (instead of wrapping).
+    ///
+    /// The hidden parts of the text are marked with `…` on either side.
+    pub fn horizontal_scroll(mut self) -> Self {
+        self.horizontal_scroll = true;
+        self
+    }
+
     /// Sets a validation callback for the input that is called when the user submits.
     /// The same as [`Input::validate_on_enter`].
     pub fn validate<V>(mut self, validator: V) -> Self
@@ -134,6 +245,44 @@ impl Input {
         self.validate(validator)
     }
 
+    /// Sets a slow validation callback (e.g. doing I/O) that is called on
+    /// a background thread when the user submits after the other checks pass.
+    ///
+    /// A spinner is shown in the footer meanwhile, and `Esc` cancels the
+    /// validation getting back to editing.
+    ///
+    /// The result is shown as soon as the validation finishes on Unix only.
+    /// On other platforms (e.g. Windows) the prompt waits for a key press
+    /// blocking, so the spinner and the result are updated on the next key.
+    ///
+    /// ```
+    /// use std::net::TcpListener;
+    /// use cliclack::Input;
+    ///
+    /// # fn test() -> std::io::Result<()> {
+    /// let is_free = |port: &String| match TcpListener::bind(format!("127.0.0.1:{port}")) {
+    ///     Ok(_) => Ok(()),
+    ///     Err(_) => Err("The port is in use"),
+    /// };
+    ///
+    /// let port: u16 = Input::new("Port")
+    ///     .validate_in_background(is_free)
+    ///     .interact()?;
+    /// # Ok(())
+    /// # }
+    /// # test().ok(); // Ignoring I/O runtime errors.
+    /// ```
+    pub fn validate_in_background<V>(mut self, validator: V) -> Self
+    where
+        V: Validate<String> + Send + Sync + 'static,
+        V::Err: ToString,
+    {
+        self.validate_in_background = Some(Arc::new(move |input: &String| {
+            validator.validate(input).map_err(|err| err.to_string())
+        }));
+        self
+    }
+
     /// Validates input while user is typing.
     pub fn validate_interactively<V>(mut self, validator: V) -> Self
     where
@@ -146,11 +295,142 @@ impl Input {
         self
     }
 
+    /// Sets a callback highlighting the input text while typing: it returns
+    /// the styled parts of the text (see [`Span`]), e.g. to highlight syntax
+    /// or the part of the input that is invalid.
+    ///
+    /// ```
+    /// use cliclack::{Input, Span};
+    /// use console::Style;
+    ///
+    /// # fn test() -> std::io::Result<()> {
+    /// let command: String = Input::new("Command")
+    ///     .highlighter(|text: &str| {
+    ///         // Dim the flags.
+    ///         let mut spans = vec![];
+    ///         let mut offset = 0;
+    ///         for word in text.split(' ') {
+    ///             if word.starts_with('-') {
+    ///                 spans.push(Span::new(offset..offset + word.len(), Style::new().dim()));
+    ///             }
+    ///             offset += word.len() + 1;
+    ///         }
+    ///         spans
+    ///     })
+    ///     .interact()?;
+    /// # Ok(())
+    /// # }
+    /// # test().ok(); // Ignoring I/O runtime errors.
+    /// ```
+    pub fn highlighter<F>(mut self, highlighter: F) -> Self
+    where
+        F: Fn(&str) -> Vec<Span> + 'static,
+    {
+        self.highlighter = Some(Box::new(highlighter));
+        self
+    }
+
+    /// Sets a mask for structured input, e.g. `(999) 999-9999` for a phone number.
+    ///
+    /// Literal characters of the template are inserted automatically, and the
+    /// cursor skips them. The remaining part of the template is shown as a hint.
+    /// See [`InputMask`] for the template syntax and presets.
+    ///
+    /// ```
+    /// use cliclack::{Input, InputMask};
+    ///
+    /// # fn test() -> std::io::Result<()> {
+    /// let phone: String = Input::new("Phone").mask("(999) 999-9999").interact()?;
+    /// let date: String = Input::new("Date").mask(InputMask::date()).interact()?;
+    /// # Ok(())
+    /// # }
+    /// # test().ok(); // Ignoring I/O runtime errors.
+    /// ```
+    pub fn mask(mut self, mask: impl Into<InputMask>) -> Self {
+        self.input.set_mask(mask.into());
+        self
+    }
+
+    /// Enables the history of submitted values stored under a given key.
+    ///
+    /// * `Up`/`Down` recall older/newer values when no autocompletion
+    ///   suggestions are shown.
+    /// * `Ctrl-R` starts the reverse search: type to find the latest matching
+    ///   value, `Ctrl-R` again to find an older one, `Enter` to take the match,
+    ///   `Esc` to stop searching.
+    ///
+    /// The values are stored in `$XDG_DATA_HOME/cliclack/history` by default
+    /// (see [`Input::history_file`]).
+    pub fn history(mut self, key: &str) -> Self {
+        self.history = Some(History::new(key));
+        self
+    }
+
+    /// Sets a custom location of the history file for [`Input::history`].
+    pub fn history_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.history_file = Some(path.into());
+        self
+    }
+
     /// Starts the prompt interaction.
+    ///
+    /// The value is parsed with [`FromStr`] on submit, and the parsing error
+    /// is shown to the user if the value can't be parsed.
     pub fn interact<T>(&mut self) -> io::Result<T>
     where
         T: FromStr,
+        T::Err: Display,
+    {
+        let parser = |input: &str| input.parse::<T>().map_err(|err| err.to_string());
+        self.interact_parsed(Box::new(parser), false)
+    }
+
+    /// Starts the prompt interaction parsing the value with a custom parser.
+    ///
+    /// The parser returns either a value or an error message shown to the user.
+    /// It's applied while typing and on submit.
+    ///
+    /// ```
+    /// use std::net::IpAddr;
+    /// use std::time::Duration;
+    /// use cliclack::Input;
+    ///
+    /// # fn test() -> std::io::Result<()> {
+    /// let timeout: Duration = Input::new("Timeout (seconds)")
+    ///     .parse_with(|input| {
+    ///         input
+    ///             .parse::<u64>()
+    ///             .map(Duration::from_secs)
+    ///             .map_err(|_| "Expected a number of seconds")
+    ///     })?;
+    ///
+    /// // Shows the `FromStr` error while typing, e.g. "invalid IP address syntax".
+    /// let address: IpAddr = Input::new("Address").parse_with(str::parse)?;
+    /// # Ok(())
+    /// # }
+    /// # test().ok(); // Ignoring I/O runtime errors.
+    /// ```
+    pub fn parse_with<T, E>(&mut self, parser: impl Fn(&str) -> Result<T, E>) -> io::Result<T>
+    where
+        E: ToString,
     {
+        let parser = move |input: &str| parser(input).map_err(|err| err.to_string());
+        self.interact_parsed(Box::new(parser), true)
+    }
+
+    /// Starts the prompt interaction parsing the value with a given parser,
+    /// also while typing if `interactive` is set.
+    pub(crate) fn interact_parsed<T>(
+        &mut self,
+        parser: ParseCallback<'_, T>,
+        interactive: bool,
+    ) -> io::Result<T> {
+        if let Some(history) = &mut self.history {
+            if let Some(path) = self.history_file.take() {
+                history.set_path(path);
+            }
+            history.load();
+        }
         if self.placeholder.is_empty() {
             if let Some(default) = &self.default {
                 self.placeholder.extend(default);
@@ -159,16 +439,28 @@ impl Input {
                 if self.multiline == Multiline::Editing {
                     self.multiline = Multiline::Preview;
                 }
+            } else if let Some(mask) = self.input.mask() {
+                self.placeholder.extend(&mask.placeholder());
             }
         }
-        <Self as PromptInteraction<T>>::interact(self)
+        Parsed {
+            prompt: self,
+            parser,
+            interactive,
+        }
+        .interact()
     }
 
-    /// Sets a list of suggestions for autocompletion.
+    /// Sets a list of suggestions for autocompletion: strings or [`Suggestion`]s
+    /// with descriptions.
     ///
     /// When the user presses Tab or uses arrow keys, they can cycle through
     /// matching suggestions.
-    pub fn autocomplete(mut self, suggestions: Vec<String>) -> Self {
+    ///
+    /// The completion of the input (see [`Autocomplete::get_completion`]) is
+    /// shown inline after the cursor, `Right`/`End` or `Tab` accept it.
+    pub fn autocomplete<S: Into<Suggestion>>(mut self, suggestions: Vec<S>) -> Self {
+        let suggestions: Vec<Suggestion> = suggestions.into_iter().map(Into::into).collect();
         self.autocompleter = Some(Box::new(suggestions));
         self
     }
@@ -176,14 +468,34 @@ impl Input {
     /// Sets a dynamic autocomplete handler function.
     ///
     /// The handler is called with the current input to get suggestions on each keystroke.
-    pub fn autocomplete_with<F>(mut self, handler: F) -> Self
+    /// For slow sources, use [`AsyncAutocomplete`](crate::AsyncAutocomplete)
+    /// with [`Input::autocompleter`] to load suggestions in the background.
+    /// The handler returns a list of strings or [`Suggestion`]s, or an error
+    /// message shown in the footer.
+    pub fn autocomplete_with<F, S>(mut self, handler: F) -> Self
     where
-        F: Fn(&str) -> crate::autocomplete::AutocompleteResult + Send + 'static,
+        F: Fn(&str) -> Result<Vec<S>, String> + Send + 'static,
+        S: Into<Suggestion> + 'static,
     {
         self.autocompleter = Some(Box::new(handler));
         self
     }
 
+    /// Sets a custom autocompletion provider, e.g. [`PathCompleter`](crate::PathCompleter).
+    pub fn autocompleter(mut self, autocompleter: impl Autocomplete + 'static) -> Self {
+        self.autocompleter = Some(Box::new(autocompleter));
+        self
+    }
+
+    /// The maximum visible number of autocompletion suggestions. Default: 10.
+    ///
+    /// If there are more suggestions, the list is scrolled following the
+    /// highlighted one, `PageUp`/`PageDown` scroll it by the page.
+    pub fn max_suggestions(mut self, height: usize) -> Self {
+        self.suggestions_view.height = height.max(1);
+        self
+    }
+
     /// Enables auto-selecting the first suggestion when pressing Enter.
     ///
     /// If there are matching suggestions, the first one will be automatically
@@ -193,33 +505,272 @@ impl Input {
         self
     }
 
-    fn get_filtered_suggestions(&mut self, query: &str) -> Vec<String> {
-        if let Some(ref mut completer) = self.autocompleter {
-            match completer.get_suggestions(query) {
-                Ok(suggestions) => suggestions,
-                Err(_) => vec![],
+    /// Appends the inline completion to the input.
+    fn accept_completion(&mut self) {
+        if let Some(completion) = self.completion.take() {
+            self.input.extend(&completion);
+            self.input.move_end();
+            self.autocompletion_index = None;
+            self.autocompletion_query.clear();
+            self.suggestions_view.start = 0;
+        }
+    }
+
+    /// Returns the rest of the completion of the input provided by the
+    /// autocompleter if the cursor is at the end of a single-line input.
+    fn get_completion(&mut self, highlighted: Option<String>) -> Option<String> {
+        let completer = self.autocompleter.as_mut()?;
+        if self.multiline != Multiline::Disabled
+            || self.history_search.is_some()
+            || self.input.is_empty()
+            || self.input.current().is_some()
+        {
+            return None;
+        }
+
+        let input = self.input.to_string();
+        let completion = completer.get_completion(&input, highlighted)?;
+        completion
+            .strip_prefix(&input)
+            .filter(|rest| !rest.is_empty())
+            .map(str::to_string)
+    }
+
+    /// Replaces the input with an older or a newer value from the history.
+    fn recall_history(&mut self, older: bool) {
+        if self.multiline != Multiline::Disabled {
+            return;
+        }
+        let Some(history) = &mut self.history else {
+            return;
+        };
+
+        let current = self.input.to_string();
+        let recalled = if older {
+            history.previous(&current)
+        } else {
+            history.next()
+        };
+
+        if let Some(value) = recalled {
+            self.input.clear();
+            self.input.extend(value);
+            self.input.move_end();
+        }
+    }
+
+    /// Handles the keys in the reverse search mode (`Ctrl-R`), where the input
+    /// is used as a search query.
+    fn on_history_search<T>(&mut self, key: &Key) -> State<T> {
+        let (Some(history), Some(search)) = (&self.history, &mut self.history_search) else {
+            return State::Active;
+        };
+        let query = self.input.to_string();
+
+        match key {
+            // Find an older match.
+            Key::Char('\u{12}') => {
+                if let Some((found, _)) = history.search(&query, search.found) {
+                    search.found = Some(found);
+                }
             }
+            // Take the match.
+            Key::Enter => {
+                let value = search.found.and_then(|found| history.get(found));
+                self.input.clear();
+                self.input.extend(value.unwrap_or(&query));
+                self.input.move_end();
+                self.history_search = None;
+            }
+            // Stop searching.
+            Key::Escape => {
+                let draft = std::mem::take(&mut search.draft);
+                self.input.clear();
+                self.input.extend(&draft);
+                self.input.move_end();
+                self.history_search = None;
+                // The cancellation is cancelled, see `interact_on_prepared`.
+                return State::Cancel;
+            }
+            // Refresh the search for the changed query.
+            _ => search.found = history.search(&query, None).map(|(found, _)| found),
+        }
+
+        State::Active
+    }
+
+    /// Opens the input in the external editor, and loads the edited text back.
+    ///
+    /// The prompt is cleared while the editor is running, and then it's drawn
+    /// again for the interaction loop to redraw it as usual.
+    fn edit_externally(&mut self) -> io::Result<()> {
+        let term = output::term();
+        if let Some(term) = &term {
+            term.clear_last_lines(
+                wrap(&self.last_frame, term.size().1 as usize)
+                    .lines()
+                    .count(),
+            )?;
+            term.show_cursor()?;
+        }
+
+        let edited = editor::edit(&self.input.to_string(), &editor::editor_command());
+
+        if let Some(term) = &term {
+            term.hide_cursor()?;
+            term.write_str(&self.last_frame)?;
+        }
+
+        // Typed in to leave the cursor at the end of the text.
+        self.input.clear();
+        edited?.chars().for_each(|chr| self.input.insert(chr));
+        Ok(())
+    }
+
+    /// Formats the multiline input like a small editor: long lines are wrapped
+    /// at the terminal width, and only the rows fitting the terminal height
+    /// are shown scrolling to the cursor. `chrome` is the number of other
+    /// lines of the prompt (the header and the footer).
+    fn format_editor<T>(&mut self, theme: &dyn Theme, state: &State<T>, chrome: usize) -> String {
+        let (height, width) = output::term()
+            .map(|term| term.size())
+            .map_or((usize::MAX, usize::MAX), |(rows, cols)| {
+                (rows as usize, cols as usize)
+            });
+
+        let gutter = if self.line_numbers {
+            let lines = self.input.to_string().split('\n').count();
+            lines.to_string().len()
         } else {
-            vec![]
+            0
+        };
+        // The bar, the spaces, and the gutter with a space after it.
+        let margin = 3 + if gutter > 0 { gutter + 1 } else { 0 };
+        self.input
+            .set_wrap_width(Some(width.saturating_sub(margin).max(1)));
+
+        let rows = self.input.visual_lines();
+        let cursor_row = self.input.visual_row();
+
+        // Leave space for the scroll indicators, and a line below the prompt.
+        let view = &mut self.editor_view;
+        view.height = height.saturating_sub(chrome + 3).max(1);
+        view.start = view.start.min(rows.len().saturating_sub(view.height));
+        if cursor_row < view.start {
+            view.start = cursor_row;
+        } else if cursor_row >= view.start + view.height {
+            view.start = cursor_row + 1 - view.height;
+        }
+        let view = self.editor_view.clone();
+
+        let mut number = 0;
+        let mut lines = vec![];
+        for (i, row) in rows.iter().enumerate() {
+            // A wrapped row continues the previous one.
+            let wrapped = i > 0 && rows[i - 1].end == row.start;
+            if !wrapped {
+                number += 1;
+            }
+            if i < view.start || i >= view.start + view.height {
+                continue;
+            }
+
+            lines.push(theme.format_input_row(
+                &state.into(),
+                &self.input,
+                row.clone(),
+                i == cursor_row,
+                (!wrapped).then_some(number),
+                gutter,
+            ));
+        }
+
+        let hidden_below = rows
+            .len()
+            .saturating_sub(view.start.saturating_add(view.height));
+        if view.start > 0 {
+            lines.insert(
+                0,
+                theme.format_input_scroll(&state.into(), view.start, true),
+            );
         }
+        if hidden_below > 0 {
+            lines.push(theme.format_input_scroll(&state.into(), hidden_below, false));
+        }
+
+        lines.concat()
     }
-}
 
-impl<T> PromptInteraction<T> for Input
-where
-    T: FromStr,
-{
-    fn input(&mut self) -> Option<&mut StringCursor> {
-        if self.multiline == Multiline::Preview {
-            return None;
+    /// Formats the single-line input scrolled horizontally to fit the terminal
+    /// width on a single row.
+    fn format_window<T>(&mut self, theme: &dyn Theme, state: &State<T>) -> String {
+        let width = output::term().map_or(usize::MAX, |term| term.size().1 as usize);
+        // The bar and the spaces after it.
+        let width = width.saturating_sub(3).max(1);
+
+        let window = self.input.horizontal_window(self.window_start, width);
+        self.window_start = window.start;
+        theme.format_input_window(&state.into(), &self.input, window)
+    }
+
+    /// Scrolls the suggestion list to make the highlighted suggestion visible.
+    fn scroll_suggestions(&mut self, index: usize) {
+        let view = &mut self.suggestions_view;
+        if index < view.start {
+            view.start = index;
+        } else if index >= view.start + view.height {
+            view.start = index + 1 - view.height;
         }
-        Some(&mut self.input)
     }
 
-    fn on(&mut self, event: &Event) -> State<T> {
+    /// Whether the autocompletion suggestions are being loaded in the background.
+    fn is_loading(&self) -> bool {
+        self.autocompleter
+            .as_ref()
+            .is_some_and(|completer| completer.is_loading())
+    }
+
+    fn get_filtered_suggestions(&mut self, query: &str) -> Vec<Suggestion> {
+        let Some(completer) = &mut self.autocompleter else {
+            return vec![];
+        };
+
+        match completer.get_rich_suggestions(query) {
+            Ok(suggestions) => {
+                self.autocomplete_error = None;
+                suggestions
+            }
+            Err(err) => {
+                self.autocomplete_error = Some(err);
+                vec![]
+            }
+        }
+    }
+}
+
+impl Input {
+    /// Handles user input parsing the value with a given parser.
+    fn on<T>(&mut self, event: &Event, parse: &dyn Fn(&str) -> Result<T, String>) -> State<T> {
         let Event::Key(key) = event;
         let mut submit = false;
 
+        if self.history_search.is_some() {
+            return self.on_history_search(key);
+        }
+
+        if self.validation.is_some() {
+            // Only `Esc` cancelling the validation is handled meanwhile.
+            if *key == Key::Escape {
+                self.validation = None;
+                return State::Cancel;
+            }
+            // The result is received here if the prompt isn't refreshed
+            // while waiting for a key (see `refresh_interval`).
+            return self.on_validated(parse).unwrap_or(State::Active);
+        }
+
+        let ctrl_x = std::mem::take(&mut self.ctrl_x);
+
         let query = self.input.to_string();
         let filter_query = if self.autocompletion_query.is_empty() {
             query.clone()
@@ -228,36 +779,21 @@ where
         };
 
         match key {
-            Key::Tab if self.autocompleter.is_some() => {
-                let filtered_suggestions = self.get_filtered_suggestions(&filter_query);
-                if filtered_suggestions.is_empty() {
-                    return State::Active;
-                }
-
-                if self.autocompletion_query.is_empty() {
-                    self.autocompletion_query = query.clone();
-                }
-
-                let new_index = match self.autocompletion_index {
-                    None => Some(0),
-                    Some(idx) => {
-                        if idx >= filtered_suggestions.len() - 1 {
-                            None
-                        } else {
-                            Some(idx + 1)
-                        }
-                    }
-                };
-                self.autocompletion_index = new_index;
-                if let Some(idx) = self.autocompletion_index {
-                    self.input.clear();
-                    self.input.extend(&filtered_suggestions[idx]);
-                }
+            // Accept the inline completion.
+            Key::Tab | Key::ArrowRight | Key::End if self.completion.is_some() => {
+                self.accept_completion();
                 return State::Active;
             }
-            Key::ArrowDown if self.autocompleter.is_some() => {
+            Key::Tab | Key::ArrowDown | Key::ArrowUp | Key::PageDown | Key::PageUp
+                if self.autocompleter.is_some() =>
+            {
                 let filtered_suggestions = self.get_filtered_suggestions(&filter_query);
                 if filtered_suggestions.is_empty() {
+                    match key {
+                        Key::ArrowUp => self.recall_history(true),
+                        Key::ArrowDown => self.recall_history(false),
+                        _ => {}
+                    }
                     return State::Active;
                 }
 
@@ -265,48 +801,53 @@ where
                     self.autocompletion_query = query.clone();
                 }
 
-                let new_index = match self.autocompletion_index {
-                    None => Some(0),
-                    Some(idx) => {
-                        if idx >= filtered_suggestions.len() - 1 {
-                            Some(0)
-                        } else {
-                            Some(idx + 1)
-                        }
+                let last = filtered_suggestions.len() - 1;
+                let page = self.suggestions_view.height;
+                self.autocompletion_index = match (key, self.autocompletion_index) {
+                    (Key::Tab, Some(idx)) if idx >= last => None,
+                    (Key::Tab | Key::ArrowDown, Some(idx)) if idx < last => Some(idx + 1),
+                    (Key::Tab | Key::ArrowDown, _) => Some(0),
+                    (Key::ArrowUp, Some(idx)) if idx > 0 => Some(idx - 1),
+                    (Key::ArrowUp, _) => Some(last),
+                    (Key::PageDown, idx) => {
+                        Some(idx.map_or(0, |idx| idx.saturating_add(page).min(last)))
                     }
+                    (Key::PageUp, idx) => Some(idx.map_or(0, |idx| idx.saturating_sub(page))),
+                    _ => self.autocompletion_index,
                 };
-                self.autocompletion_index = new_index;
                 if let Some(idx) = self.autocompletion_index {
                     self.input.clear();
-                    self.input.extend(&filtered_suggestions[idx]);
+                    self.input.extend(filtered_suggestions[idx].value());
+                    self.input.move_end();
+                    self.scroll_suggestions(idx);
                 }
                 return State::Active;
             }
-            Key::ArrowUp if self.autocompleter.is_some() => {
-                let filtered_suggestions = self.get_filtered_suggestions(&filter_query);
-                if filtered_suggestions.is_empty() {
-                    return State::Active;
-                }
-
-                if self.autocompletion_query.is_empty() {
-                    self.autocompletion_query = query.clone();
-                }
-
-                let new_index = match self.autocompletion_index {
-                    None => Some(filtered_suggestions.len() - 1),
-                    Some(idx) => {
-                        if idx == 0 {
-                            Some(filtered_suggestions.len() - 1)
-                        } else {
-                            Some(idx - 1)
-                        }
-                    }
-                };
-                self.autocompletion_index = new_index;
-                if let Some(idx) = self.autocompletion_index {
-                    self.input.clear();
-                    self.input.extend(&filtered_suggestions[idx]);
+            Key::ArrowUp | Key::ArrowDown if self.history.is_some() => {
+                self.recall_history(*key == Key::ArrowUp);
+            }
+            // Ctrl-R
+            Key::Char('\u{12}')
+                if self.history.is_some() && self.multiline == Multiline::Disabled =>
+            {
+                self.history_search = Some(HistorySearch {
+                    draft: self.input.to_string(),
+                    found: None,
+                });
+                self.input.clear();
+                return State::Active;
+            }
+            // Ctrl-X
+            Key::Char('\u{18}') if self.multiline != Multiline::Disabled => {
+                self.ctrl_x = true;
+                return State::Active;
+            }
+            // Ctrl-X Ctrl-E (Ctrl-E is read as `End`)
+            Key::End if ctrl_x && self.multiline != Multiline::Disabled => {
+                if let Err(err) = self.edit_externally() {
+                    return State::Error(format!("Failed to open the editor: {err}"));
                 }
+                self.multiline = Multiline::Editing;
                 return State::Active;
             }
             Key::Escape if self.multiline == Multiline::Editing => {
@@ -327,10 +868,18 @@ where
             Key::Char(c) if !c.is_ascii_control() => {
                 self.autocompletion_index = None;
                 self.autocompletion_query.clear();
+                self.suggestions_view.start = 0;
+                if let Some(history) = &mut self.history {
+                    history.reset();
+                }
             }
             Key::Backspace => {
                 self.autocompletion_index = None;
                 self.autocompletion_query.clear();
+                self.suggestions_view.start = 0;
+                if let Some(history) = &mut self.history {
+                    history.reset();
+                }
             }
             _ => {}
         }
@@ -339,7 +888,7 @@ where
             let suggestions = self.get_filtered_suggestions(&self.input.to_string());
             if !suggestions.is_empty() {
                 self.input.clear();
-                self.input.extend(&suggestions[0]);
+                self.input.extend(suggestions[0].value());
             }
         }
 
@@ -360,28 +909,86 @@ where
                 return State::Error(err);
             }
 
-            if self.input.to_string().parse::<T>().is_err() {
-                return State::Error("Invalid value format".to_string());
+            if let Err(err) = parse(&self.input.to_string()) {
+                return State::Error(err);
             }
         }
 
         if submit {
+            if !self.input.is_empty() && !self.input.is_mask_complete() {
+                let placeholder = self.input.mask().map(InputMask::placeholder);
+                return State::Error(format!(
+                    "Expected format: {}",
+                    placeholder.unwrap_or_default()
+                ));
+            }
+
+            if let Some(min) = self.min_length {
+                let value = self.input.to_string();
+                if let (false, Err(err)) = (
+                    value.is_empty(),
+                    Validator::min_length(min).validate(&value),
+                ) {
+                    return State::Error(err);
+                }
+            }
+
             if let Some(validator) = &self.validate_on_enter {
                 if let Err(err) = validator(&self.input.to_string()) {
                     return State::Error(err);
                 }
             }
 
-            match self.input.to_string().parse::<T>() {
-                Ok(value) => return State::Submit(value),
-                Err(_) => return State::Error("Invalid value format".to_string()),
+            if let Some(validator) = &self.validate_in_background {
+                if let Err(err) = parse(&self.input.to_string()) {
+                    return State::Error(err);
+                }
+
+                let validator = validator.clone();
+                let input = self.input.to_string();
+                let (sender, receiver) = mpsc::channel();
+                thread::spawn(move || sender.send(validator(&input)));
+                self.validation = Some(receiver);
+                return State::Active;
             }
+
+            return self.submit(parse);
         }
 
         State::Active
     }
 
-    fn render(&mut self, state: &State<T>) -> String {
+    /// Parses the input submitting the value.
+    fn submit<T>(&mut self, parse: &dyn Fn(&str) -> Result<T, String>) -> State<T> {
+        match parse(&self.input.to_string()) {
+            Ok(value) => {
+                if let Some(history) = &mut self.history {
+                    // The history is a convenience, don't fail the prompt.
+                    history.save(&self.input.to_string()).ok();
+                }
+                State::Submit(value)
+            }
+            Err(err) => State::Error(err),
+        }
+    }
+
+    /// Checks the result of the background validation submitting the value
+    /// if it has passed, `None` if it's still running.
+    fn on_validated<T>(&mut self, parse: &dyn Fn(&str) -> Result<T, String>) -> Option<State<T>> {
+        let result = match self.validation.as_ref()?.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err("The validation has failed".to_string()),
+        };
+        self.validation = None;
+
+        Some(match result {
+            Ok(()) => self.submit(parse),
+            Err(err) => State::Error(err),
+        })
+    }
+
+    fn render<T>(&mut self, state: &State<T>) -> String {
         let theme = THEME.read().unwrap();
 
         let filter_query = if self.autocompletion_query.is_empty() {
@@ -390,53 +997,483 @@ where
             self.autocompletion_query.clone()
         };
 
-        let filtered_suggestions: Vec<String> = self.get_filtered_suggestions(&filter_query);
+        // The input is a search query in the history search mode.
+        let filtered_suggestions: Vec<Suggestion> = if self.history_search.is_some() {
+            vec![]
+        } else {
+            self.get_filtered_suggestions(&filter_query)
+        };
+
+        let loading = self.history_search.is_none() && self.is_loading();
 
         let suggestions = if !matches!(state, State::Active) {
             String::new()
+        } else if loading {
+            self.loading_frame += 1;
+            let indicator = theme.format_autocomplete_loading(&state.into(), self.loading_frame);
+            format!("{indicator}\n")
         } else if filtered_suggestions.is_empty() {
             String::new()
         } else {
-            let suggestions_text = filtered_suggestions
+            // Keep the page within the list if it's shrunk.
+            let view = &mut self.suggestions_view;
+            view.start = view
+                .start
+                .min(filtered_suggestions.len().saturating_sub(view.height));
+            let view = self.suggestions_view.clone();
+            let visible = filtered_suggestions
                 .iter()
                 .enumerate()
-                .map(|(i, choice)| {
-                    let is_selected = self.autocompletion_index.map_or(false, |idx| idx == i);
-                    theme.format_autocomplete_item(&state.into(), choice, is_selected)
-                })
-                .collect::<Vec<_>>()
-                .join("\n");
+                .skip(view.start)
+                .take(view.height);
+
+            // Align the descriptions in a column.
+            let width = visible
+                .clone()
+                .map(|(_, suggestion)| measure_text_width(suggestion.label()))
+                .max()
+                .unwrap_or_default();
+
+            let hidden_above = view.start;
+            let hidden_below = filtered_suggestions
+                .len()
+                .saturating_sub(view.start.saturating_add(view.height));
+
+            let mut lines = vec![];
+            if hidden_above > 0 {
+                lines.push(theme.format_autocomplete_scroll(&state.into(), hidden_above, true));
+            }
+            for (i, suggestion) in visible {
+                let is_selected = self.autocompletion_index == Some(i);
+                lines.push(theme.format_autocomplete_suggestion(
+                    &state.into(),
+                    suggestion.label(),
+                    &matched_chars(&filter_query, suggestion.label()),
+                    suggestion.hint(),
+                    width,
+                    is_selected,
+                ));
+            }
+            if hidden_below > 0 {
+                lines.push(theme.format_autocomplete_scroll(&state.into(), hidden_below, false));
+            }
+            let suggestions_text = lines.join("\n");
             format!("{}\n", suggestions_text)
         };
 
+        self.completion = match state {
+            State::Active | State::Error(_) => {
+                let highlighted = self
+                    .autocompletion_index
+                    .and_then(|ix| filtered_suggestions.get(ix))
+                    .map(|suggestion| suggestion.value().to_string());
+                self.get_completion(highlighted)
+            }
+            _ => None,
+        };
+
+        if let Some(highlighter) = &self.highlighter {
+            let text = self.input.to_string();
+            self.input
+                .set_highlights(char_ranges(&text, highlighter(&text)));
+        }
+
         let prompt = theme.format_header(&state.into(), &self.prompt);
         let input = if self.input.is_empty() {
             theme.format_placeholder(&state.into(), &self.placeholder)
+        } else if let Some(remaining) = self.input.mask_remaining() {
+            theme.format_masked_input(&state.into(), &self.input, &remaining)
+        } else if let Some(completion) = &self.completion {
+            theme.format_input_with_completion(&state.into(), &self.input, completion)
+        } else if self.multiline != Multiline::Disabled
+            && matches!(state, State::Active | State::Error(_))
+        {
+            let footer_height = match state {
+                State::Error(err) => err.lines().count().max(1),
+                _ => 1,
+            };
+            let chrome = prompt.lines().count() + footer_height;
+            self.format_editor(theme.as_ref(), state, chrome)
+        } else if self.horizontal_scroll && matches!(state, State::Active | State::Error(_)) {
+            self.format_window(theme.as_ref(), state)
         } else {
             theme.format_input(&state.into(), &self.input)
         };
 
         let mut footer_message = match self.multiline {
-            Multiline::Editing => "[Esc](Preview)",
-            Multiline::Preview => "[Enter](Submit)",
+            Multiline::Editing => "[Esc](Preview) [Ctrl-X Ctrl-E](Editor)",
+            Multiline::Preview => "[Enter](Submit) [Ctrl-X Ctrl-E](Editor)",
             _ => "",
         };
 
-        if self.autocompleter.is_some() && !filtered_suggestions.is_empty() {
+        let has_suggestions = loading || !filtered_suggestions.is_empty();
+        if self.autocompleter.is_some() && has_suggestions {
             footer_message = "";
         }
 
-        let footer = theme.format_footer_with_message(&state.into(), footer_message);
+        let autocomplete_error = match state {
+            State::Active if self.history_search.is_none() => self
+                .autocomplete_error
+                .as_ref()
+                .map(|err| theme.format_autocomplete_error(&state.into(), err)),
+            _ => None,
+        };
+        if let Some(autocomplete_error) = &autocomplete_error {
+            footer_message = autocomplete_error;
+        }
 
-        let footer = if matches!(state, State::Active)
-            && self.autocompleter.is_some()
-            && !filtered_suggestions.is_empty()
-        {
-            theme.format_footer_with_message(&state.into(), "\r└ ◇")
-        } else {
-            footer
+        let history_match = self.history_search.as_ref().map(|search| {
+            let found = search.found.and_then(|ix| self.history.as_ref()?.get(ix));
+            format!("(reverse search) {}", found.unwrap_or("no match"))
+        });
+        if let Some(history_match) = &history_match {
+            footer_message = history_match;
+        }
+
+        let validating = match state {
+            State::Active if self.validation.is_some() => {
+                self.loading_frame += 1;
+                Some(theme.format_validation_progress(&state.into(), self.loading_frame))
+            }
+            _ => None,
+        };
+        if let Some(validating) = &validating {
+            footer_message = validating;
+        }
+
+        let count = match state {
+            State::Active | State::Error(_)
+                if self.validation.is_none()
+                    && (self.min_length.is_some() || self.max_length.is_some()) =>
+            {
+                Some(theme.format_character_count(
+                    &state.into(),
+                    self.input.len(),
+                    self.min_length,
+                    self.max_length,
+                ))
+            }
+            _ => None,
+        };
+        let counter = count.as_ref().map(|count| match footer_message {
+            "" => count.clone(),
+            message => format!("{message} {count}"),
+        });
+        if let Some(counter) = &counter {
+            footer_message = counter;
+        }
+
+        let footer = match (state, &count) {
+            // The error replaces the footer message, so the counter follows the error.
+            (State::Error(err), Some(count)) => {
+                theme.format_footer_with_message(&ThemeState::Error(format!("{err} {count}")), "")
+            }
+            _ => theme.format_footer_with_message(&state.into(), footer_message),
         };
 
-        prompt + &input + &footer + &suggestions
+        let footer =
+            if matches!(state, State::Active) && self.autocompleter.is_some() && has_suggestions {
+                theme.format_footer_with_message(&state.into(), "\r└ ◇")
+            } else {
+                footer
+            };
+
+        self.last_frame = prompt + &input + &footer + &suggestions;
+        self.last_frame.clone()
+    }
+}
+
+/// The input prompt interaction with a given parser of the value.
+struct Parsed<'a, T> {
+    prompt: &'a mut Input,
+    parser: ParseCallback<'a, T>,
+    /// Whether the value is parsed while typing.
+    interactive: bool,
+}
+
+impl<T> PromptInteraction<T> for Parsed<'_, T> {
+    fn input(&mut self) -> Option<&mut StringCursor> {
+        if self.prompt.multiline == Multiline::Preview || self.prompt.validation.is_some() {
+            return None;
+        }
+        Some(&mut self.prompt.input)
+    }
+
+    fn transcript(&self) -> Option<(String, String)> {
+        Some((self.prompt.prompt.clone(), self.prompt.input.to_string()))
+    }
+
+    fn on(&mut self, event: &Event) -> State<T> {
+        let state = self.prompt.on(event, &self.parser);
+
+        let typing = matches!(state, State::Active)
+            && self.prompt.validation.is_none()
+            && self.prompt.history_search.is_none()
+            && !self.prompt.input.is_empty();
+        if self.interactive && typing {
+            if let Err(err) = (self.parser)(&self.prompt.input.to_string()) {
+                return State::Error(err);
+            }
+        }
+
+        state
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        // Animate the loading indicator and show the results once loaded.
+        let loading = self.prompt.is_loading() || self.prompt.validation.is_some();
+        loading.then_some(LOADING_REFRESH_INTERVAL)
+    }
+
+    fn on_refresh(&mut self) -> Option<State<T>> {
+        self.prompt.on_validated(&self.parser)
+    }
+
+    fn render(&mut self, state: &State<T>) -> String {
+        self.prompt.render(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submit<T>(
+        input: &mut Input,
+        text: &str,
+        parse: &dyn Fn(&str) -> Result<T, String>,
+    ) -> State<T> {
+        input.input.extend(text);
+        input.on(&Event::Key(Key::Enter), parse)
+    }
+
+    #[test]
+    fn parse_error() {
+        let parse = |input: &str| input.parse::<u16>().map_err(|err| err.to_string());
+        match submit(&mut Input::new("Port"), "8o80", &parse) {
+            State::Error(err) => assert_eq!(err, "invalid digit found in string"),
+            _ => panic!("expected a parse error"),
+        }
+        match submit(&mut Input::new("Port"), "8080", &parse) {
+            State::Submit(port) => assert_eq!(port, 8080),
+            _ => panic!("expected a port"),
+        }
+    }
+
+    #[test]
+    fn parse_while_typing() {
+        let mut input = Input::new("Port");
+        let mut parsed = Parsed {
+            prompt: &mut input,
+            parser: Box::new(|input: &str| input.parse::<u16>().map_err(|err| err.to_string())),
+            interactive: true,
+        };
+        parsed.prompt.input.insert('8');
+        assert!(matches!(
+            parsed.on(&Event::Key(Key::Char('8'))),
+            State::Active
+        ));
+        parsed.prompt.input.insert('o');
+        match parsed.on(&Event::Key(Key::Char('o'))) {
+            State::Error(err) => assert_eq!(err, "invalid digit found in string"),
+            _ => panic!("expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn inline_completion() {
+        let parse = |input: &str| Ok::<_, String>(input.to_string());
+        let languages = vec!["rust".to_string(), "ruby".to_string()];
+        let mut input = Input::new("Language").autocomplete(languages);
+
+        input.input.extend("ru");
+        input.input.move_end();
+        input.render(&State::<String>::Active);
+        assert_eq!(input.completion.as_deref(), Some("st"));
+
+        input.on(&Event::Key(Key::End), &parse);
+        assert_eq!(input.input.to_string(), "rust");
+        input.render(&State::<String>::Active);
+        assert_eq!(input.completion, None);
+    }
+
+    #[test]
+    fn rich_suggestions() {
+        let commands = vec![Suggestion::new("run")
+            .display("run, r")
+            .description("Run a binary")];
+        let mut input = Input::new("Command").autocomplete(commands);
+        input.input.extend("r");
+        let frame = console::strip_ansi_codes(&input.render(&State::<String>::Active)).to_string();
+        assert!(frame.contains("run, r  Run a binary"));
+
+        let mut input =
+            Input::new("Crate").autocomplete_with(|_: &str| -> Result<Vec<String>, _> {
+                Err("Index is unavailable".to_string())
+            });
+        let frame = console::strip_ansi_codes(&input.render(&State::<String>::Active)).to_string();
+        assert!(frame.contains("Index is unavailable"));
+    }
+
+    #[test]
+    fn editor_viewport() {
+        let parse = |input: &str| Ok::<_, String>(input.to_string());
+        let text = (1..=200).map(|i| format!("line {i}")).collect::<Vec<_>>();
+        let mut input = Input::new("Text").multiline().line_numbers();
+        text.join("\n")
+            .chars()
+            .for_each(|chr| input.input.insert(chr));
+
+        let frame = console::strip_ansi_codes(&input.render(&State::<String>::Active)).to_string();
+        assert!(frame.contains("200 line 200"));
+        assert!(!frame.contains("  1 line 1\n"));
+        assert!(frame.contains("more lines"));
+
+        for _ in 0..199 {
+            input.input.move_up();
+            input.on(&Event::Key(Key::ArrowUp), &parse);
+            input.render(&State::<String>::Active);
+        }
+        assert_eq!(input.editor_view.start, 0);
+    }
+
+    #[test]
+    fn length_limits() {
+        let parse = |input: &str| Ok::<_, String>(input.to_string());
+        let mut input = Input::new("Slug").min_length(3).max_length(5);
+        for c in "abcdefg".chars() {
+            input.input.insert(c);
+        }
+        assert_eq!(input.input.to_string(), "abcde");
+        let frame = console::strip_ansi_codes(&input.render(&State::<String>::Active)).to_string();
+        assert!(frame.contains("5/5"));
+
+        input.input.clear();
+        input.input.extend("ab");
+        let frame = console::strip_ansi_codes(&input.render(&State::<String>::Active)).to_string();
+        assert!(frame.contains("2/5 (min 3)"));
+        let state = input.on(&Event::Key(Key::Enter), &parse);
+        match &state {
+            State::Error(err) => assert_eq!(err, "The value must be at least 3 characters long"),
+            _ => panic!("expected a length error"),
+        }
+        // The counter stays next to the error.
+        let frame = console::strip_ansi_codes(&input.render(&state)).to_string();
+        assert!(frame.contains("The value must be at least 3 characters long 2/5 (min 3)"));
+
+        // An empty input isn't checked (unless it's required).
+        input.input_required = false;
+        input.input.clear();
+        assert!(matches!(
+            input.on(&Event::Key(Key::Enter), &parse),
+            State::Submit(_)
+        ));
+    }
+
+    #[test]
+    fn horizontal_scroll() {
+        let mut input = Input::new("Token").horizontal_scroll();
+        input.input.extend(&"x".repeat(500));
+        input.input.move_end();
+
+        let render = |input: &mut Input| {
+            let frame = input.render(&State::<String>::Active);
+            console::strip_ansi_codes(&frame)
+                .lines()
+                .filter(|line| line.contains('x'))
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>()
+        };
+        let rows = render(&mut input);
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].contains("  …x"));
+
+        input.input.move_home();
+        let rows = render(&mut input);
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].ends_with("x…"));
+        assert_eq!(input.window_start, 0);
+    }
+
+    #[test]
+    fn highlighting() {
+        console::set_colors_enabled(true);
+        let mut input = Input::new("Cron").highlighter(|text: &str| {
+            let start = text.find('x').unwrap_or(text.len());
+            vec![Span::new(start..start + 1, console::Style::new().red())]
+        });
+        input.input.extend("1 x *");
+        input.input.move_end();
+
+        let frame = input.render(&State::<String>::Active);
+        assert!(frame.contains(&console::style("x").red().to_string()));
+        assert!(frame.contains(&console::style(' ').reverse().to_string()));
+    }
+
+    #[test]
+    fn background_validation() {
+        let parse = |input: &str| Ok::<_, String>(input.to_string());
+        let mut input = Input::new("Name").validate_in_background(|name: &String| {
+            thread::sleep(Duration::from_millis(50));
+            match name.as_str() {
+                "taken" => Err("The name is taken"),
+                _ => Ok(()),
+            }
+        });
+
+        let validate = |input: &mut Input, text: &str| {
+            input.input.clear();
+            let state = submit(input, text, &parse);
+            assert!(matches!(state, State::Active));
+            assert!(input.render(&state).contains("Validating"));
+            loop {
+                if let Some(state) = input.on_validated(&parse) {
+                    return state;
+                }
+                thread::sleep(Duration::from_millis(10));
+            }
+        };
+
+        match validate(&mut input, "taken") {
+            State::Error(err) => assert_eq!(err, "The name is taken"),
+            _ => panic!("expected a validation error"),
+        }
+        match validate(&mut input, "free") {
+            State::Submit(name) => assert_eq!(name, "free"),
+            _ => panic!("expected a submitted name"),
+        }
+
+        // The result is received on a key press as well.
+        input.input.clear();
+        submit(&mut input, "free", &parse);
+        thread::sleep(Duration::from_millis(200));
+        match input.on(&Event::Key(Key::Enter), &parse) {
+            State::Submit(name) => assert_eq!(name, "free"),
+            _ => panic!("expected a submitted name"),
+        }
+
+        // Esc cancels the validation.
+        input.input.clear();
+        submit(&mut input, "taken", &parse);
+        input.on(&Event::Key(Key::Escape), &parse);
+        assert!(input.validation.is_none());
+    }
+
+    #[test]
+    fn scrolling_suggestions() {
+        let parse = |input: &str| Ok::<_, String>(input.to_string());
+        let items: Vec<String> = (0..30).map(|i| format!("item {i:02}")).collect();
+        let mut input = Input::new("Item").autocomplete(items).max_suggestions(5);
+        input.input.extend("item");
+
+        input.on(&Event::Key(Key::PageDown), &parse);
+        input.on(&Event::Key(Key::PageDown), &parse);
+        assert_eq!(input.input.to_string(), "item 05");
+        assert_eq!(input.suggestions_view.start, 1);
+
+        let frame = console::strip_ansi_codes(&input.render(&State::<String>::Active)).to_string();
+        assert!(frame.contains("↑ 1 more"));
+        assert!(frame.contains("↓ 24 more"));
+        assert_eq!(matched_chars("it5", "item 05"), vec![0, 1, 6]);
     }
 }
@@ -6,8 +6,8 @@
 //!
 //! 💎 Fancy minimal UI.<br>
 //! ✅ Simple API.<br>
-//! 🧱 Comes with [`input`](fn@input), [`password`](fn@password),
-//!    [`confirm`](fn@confirm), [`select`](fn@select),
+//! 🧱 Comes with [`input`](fn@input), [`number`](fn@number), [`path`](fn@path),
+//!    [`password`](fn@password), [`confirm`](fn@confirm), [`select`](fn@select),
 //!    [`multiselect`](fn@multiselect), [`spinner`](fn@spinner),
 //!    [`progress_bar`](fn@progress_bar), and
 //!    [`multi_progress`](fn@multi_progress) prompts.<br>
@@ -51,6 +51,10 @@
 //! trying to parse it into a target type.
 //!
 //! Multiline editing can be enabled by [`Input::multiline`].
+//! `Ctrl-X Ctrl-E` opens the text in the external editor (`$VISUAL` or `$EDITOR`).
+//!
+//! Validation accepts closures as well as the reusable, composable checks
+//! of [`Validator`] (e.g. [`Validator::email`] or [`Validator::range`]).
 //!
 //! ```
 //! use cliclack::input;
@@ -71,10 +75,51 @@
 //! # test().ok(); // Ignoring I/O runtime errors.
 //! ```
 //!
+//! ## Number
+//!
+//! The number prompt accepts a number in a given range. `Up`/`Down` keys
+//! change the value by a step.
+//!
+//! ```
+//! # fn test() -> std::io::Result<()> {
+//! use cliclack::number;
+//!
+//! let replicas: u32 = number("How many replicas?")
+//!     .min(1)
+//!     .max(10)
+//!     .default_value(3)
+//!     .interact()?;
+//! # Ok(())
+//! # }
+//! # test().ok(); // Ignoring I/O runtime errors.
+//! ```
+//!
+//! ## Path
+//!
+//! The path prompt completes files and directories with `Tab`, expands `~`,
+//! and checks the path on submit.
+//!
+//! ```
+//! # fn test() -> std::io::Result<()> {
+//! use cliclack::path;
+//!
+//! let config = path("Where is the config?")
+//!     .extensions(["toml"])
+//!     .file()
+//!     .must_exist()
+//!     .interact()?;
+//! # Ok(())
+//! # }
+//! # test().ok(); // Ignoring I/O runtime errors.
+//! ```
+//!
 //! ## Password
 //!
 //! The password prompt is similar to the input prompt, but it doesn't echo the
-//! actual characters.
+//! actual characters. It can also ask to repeat the password
+//! ([`Password::confirm`]), show its strength ([`Password::strength`]), and
+//! reveal it with `Ctrl-R` ([`Password::allow_reveal`]).
+//! The password is returned as a [`SecretString`] which is zeroed in memory on drop.
 //!
 //! ```
 //! # fn test() -> std::io::Result<()> {
@@ -222,6 +267,34 @@
 //! # test().ok(); // Ignoring I/O runtime errors.
 //! ```
 //!
+//! ## Output
+//!
+//! Prompts, messages and progress bars are written to stderr by default.
+//! The output target can be changed with [`set_output`], e.g. to stdout,
+//! a custom [`console::Term`], or any non-interactive [`std::io::Write`] sink.
+//!
+//! ```
+//! use cliclack::{set_output, Output};
+//!
+//! set_output(Output::Stdout);
+//! ```
+//!
+//! ## Transcript
+//!
+//! A plain text or Markdown record of the session (answers, logs, notes,
+//! progress outcomes) can be collected with [`set_transcript`], e.g. for audit.
+//!
+//! ```
+//! # fn test() -> std::io::Result<()> {
+//! use cliclack::{set_transcript, Transcript};
+//!
+//! set_transcript(Transcript::file("session.md")?.markdown());
+//! // ...
+//! cliclack::reset_transcript()?; // Reports a failed write, if any.
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! ## Theme
 //!
 //! Custom UI is supported via the [`Theme`] trait.
@@ -255,57 +328,89 @@
 
 mod autocomplete;
 mod confirm;
+mod editor;
 mod filter;
+mod highlight;
+mod history;
 mod input;
+mod mask;
 mod multiprogress;
 mod multiselect;
+mod number;
+mod output;
 mod password;
+mod path;
 mod progress;
 mod prompt;
+mod secret;
 mod select;
 mod theme;
+mod transcript;
 mod validate;
+mod validator;
 mod view;
 
-use console::Term;
 use std::fmt::Display;
 use std::io;
 
 use theme::THEME;
+use transcript::Entry;
 
 // 🎨 Export of the theme API.
 pub use theme::{reset_theme, set_theme, Theme, ThemeState};
 // 🎨 Re-export for some `Theme` trait methods.
 pub use prompt::cursor::StringCursor;
+// 🖥️ Export of the output target API.
+pub use output::{reset_output, set_output, Output};
+// 📝 Export of the session transcript API.
+pub use transcript::{
+    reset_transcript, set_transcript, Transcript, TranscriptBuffer, TranscriptFormat,
+};
 
-pub use autocomplete::{Autocomplete, AutocompleteResult};
+pub use autocomplete::{
+    AsyncAutocomplete, Autocomplete, AutocompleteResult, Suggestion, SuggestionResult,
+};
 pub use confirm::Confirm;
+pub use highlight::Span;
 pub use input::Input;
+pub use mask::InputMask;
 pub use multiprogress::MultiProgress;
 pub use multiselect::MultiSelect;
-pub use password::Password;
+pub use number::{Number, Numeric};
+pub use password::{Password, Strength};
+pub use path::{PathCompleter, PathInput, PathKind};
 pub use progress::ProgressBar;
+pub use secret::SecretString;
 pub use select::Select;
 pub use validate::Validate;
+pub use validator::Validator;
 
 fn term_write(line: impl Display) -> io::Result<()> {
-    Term::stderr().write_str(line.to_string().as_str())
+    output::write_str(line.to_string().as_str())
 }
 
-/// Clears the terminal.
+/// Clears the terminal of the output target (see [`set_output`]).
+///
+/// Does nothing if the output is not a terminal.
 pub fn clear_screen() -> io::Result<()> {
-    Term::stdout().clear_screen()?;
-    Term::stderr().clear_screen()
+    match output::term() {
+        Some(term) => term.clear_screen(),
+        None => Ok(()),
+    }
 }
 
 /// Prints a header of the prompt sequence.
 pub fn intro(title: impl Display) -> io::Result<()> {
-    term_write(THEME.read().unwrap().format_intro(&title.to_string()))
+    term_write(THEME.read().unwrap().format_intro(&title.to_string()))?;
+    transcript::record(Entry::Intro(title.to_string()));
+    Ok(())
 }
 
 /// Prints a footer of the prompt sequence.
 pub fn outro(message: impl Display) -> io::Result<()> {
-    term_write(THEME.read().unwrap().format_outro(&message.to_string()))
+    term_write(THEME.read().unwrap().format_outro(&message.to_string()))?;
+    transcript::record(Entry::Outro(message.to_string()));
+    Ok(())
 }
 
 /// Prints a footer of the prompt sequence with a failure style.
@@ -315,7 +420,9 @@ pub fn outro_cancel(message: impl Display) -> io::Result<()> {
             .read()
             .unwrap()
             .format_outro_cancel(&message.to_string()),
-    )
+    )?;
+    transcript::record(Entry::OutroCancel(message.to_string()));
+    Ok(())
 }
 
 /// Prints a footer of the prompt sequence with a note style.
@@ -325,7 +432,12 @@ pub fn outro_note(prompt: impl Display, message: impl Display) -> io::Result<()>
             .read()
             .unwrap()
             .format_outro_note(&prompt.to_string(), &message.to_string()),
-    )
+    )?;
+    transcript::record(Entry::Note {
+        prompt: prompt.to_string(),
+        message: message.to_string(),
+    });
+    Ok(())
 }
 
 /// Constructs a new [`Input`] prompt.
@@ -335,6 +447,20 @@ pub fn input(prompt: impl Display) -> Input {
     Input::new(prompt)
 }
 
+/// Constructs a new [`Number`] prompt.
+///
+/// See [`Number`] for chainable methods.
+pub fn number<T: Numeric>(prompt: impl Display) -> Number<T> {
+    Number::new(prompt)
+}
+
+/// Constructs a new [`PathInput`] prompt.
+///
+/// See [`PathInput`] for chainable methods.
+pub fn path(prompt: impl Display) -> PathInput {
+    PathInput::new(prompt)
+}
+
 /// Constructs a new [`Password`] prompt.
 ///
 /// See [`Password`] for chainable methods.
@@ -391,55 +517,65 @@ pub fn note(prompt: impl Display, message: impl Display) -> io::Result<()> {
             .read()
             .unwrap()
             .format_note(&prompt.to_string(), &message.to_string()),
-    )
+    )?;
+    transcript::record(Entry::Note {
+        prompt: prompt.to_string(),
+        message: message.to_string(),
+    });
+    Ok(())
 }
 
 /// Non-interactive information messages of different styles.
 pub mod log {
     use super::*;
 
-    fn log(text: impl Display, symbol: impl Display) -> io::Result<()> {
+    fn log(text: impl Display, symbol: impl Display, level: &'static str) -> io::Result<()> {
         term_write(
             THEME
                 .read()
                 .unwrap()
                 .format_log(&text.to_string(), &symbol.to_string()),
-        )
+        )?;
+        transcript::record(Entry::Log {
+            level,
+            text: text.to_string(),
+        });
+        Ok(())
     }
 
     /// Prints a remark message.
     pub fn remark(text: impl Display) -> io::Result<()> {
         let symbol = THEME.read().unwrap().remark_symbol();
-        log(text, symbol)
+        log(text, symbol, "remark")
     }
 
     /// Prints an info message.
     pub fn info(text: impl Display) -> io::Result<()> {
         let symbol = THEME.read().unwrap().info_symbol();
-        log(text, symbol)
+        log(text, symbol, "info")
     }
 
     /// Prints a warning message.
     pub fn warning(message: impl Display) -> io::Result<()> {
         let symbol = THEME.read().unwrap().warning_symbol();
-        log(message, symbol)
+        log(message, symbol, "warning")
     }
 
     /// Prints an error message.
     pub fn error(message: impl Display) -> io::Result<()> {
         let symbol = THEME.read().unwrap().error_symbol();
-        log(message, symbol)
+        log(message, symbol, "error")
     }
 
     /// Prints a success message.
     pub fn success(message: impl Display) -> io::Result<()> {
         let symbol = THEME.read().unwrap().active_symbol();
-        log(message, symbol)
+        log(message, symbol, "success")
     }
 
     /// Prints a submitted step message.
     pub fn step(message: impl Display) -> io::Result<()> {
         let symbol = THEME.read().unwrap().submit_symbol();
-        log(message, symbol)
+        log(message, symbol, "step")
     }
 }
@@ -0,0 +1,266 @@
+/// A position of the mask template.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Token {
+    /// `9`: a digit.
+    Digit,
+    /// `#`: an optional digit.
+    OptionalDigit,
+    /// `a`: a letter.
+    Letter,
+    /// `*`: a letter or a digit.
+    Alphanumeric,
+    /// `h`: a hexadecimal digit.
+    Hex,
+    /// Any other character (or an escaped one, e.g. `\9`), inserted automatically.
+    Literal(char),
+}
+
+impl Token {
+    fn accepts(&self, chr: char) -> bool {
+        match self {
+            Token::Digit | Token::OptionalDigit => chr.is_ascii_digit(),
+            Token::Letter => chr.is_alphabetic(),
+            Token::Alphanumeric => chr.is_alphanumeric(),
+            Token::Hex => chr.is_ascii_hexdigit(),
+            Token::Literal(_) => false,
+        }
+    }
+
+    fn is_slot(&self) -> bool {
+        !matches!(self, Token::Literal(_))
+    }
+}
+
+/// The placeholder character of a mask slot which hasn't been filled yet.
+const SLOT_PLACEHOLDER: char = '_';
+
+/// The result of applying a mask to a text.
+pub(crate) struct Conformed {
+    /// The text with literals inserted and unacceptable characters dropped.
+    pub value: Vec<char>,
+    /// Whether each character of the value is a literal of the template.
+    pub literals: Vec<bool>,
+    /// The cursor position in the conformed value.
+    pub cursor: usize,
+    /// The position of the template next to the value.
+    next: usize,
+}
+
+/// A template of a structured input, e.g. a phone number or a date.
+///
+/// The template consists of:
+/// * `9` – a digit,
+/// * `#` – an optional digit (typing the next literal skips it),
+/// * `a` – a letter,
+/// * `*` – a letter or a digit,
+/// * `h` – a hexadecimal digit,
+/// * any other character is a literal inserted automatically
+///   (use `\` to escape the characters above, e.g. `\9`).
+///
+/// See [`Input::mask`](crate::Input::mask).
+#[derive(Clone, Debug)]
+pub struct InputMask {
+    tokens: Vec<Token>,
+}
+
+impl InputMask {
+    /// Creates a mask from a template, e.g. `(999) 999-9999`.
+    pub fn new(template: &str) -> Self {
+        let mut tokens = vec![];
+        let mut chars = template.chars();
+        while let Some(chr) = chars.next() {
+            tokens.push(match chr {
+                '9' => Token::Digit,
+                '#' => Token::OptionalDigit,
+                'a' => Token::Letter,
+                '*' => Token::Alphanumeric,
+                'h' => Token::Hex,
+                '\\' => Token::Literal(chars.next().unwrap_or('\\')),
+                _ => Token::Literal(chr),
+            });
+        }
+        Self { tokens }
+    }
+
+    /// A date in the ISO 8601 format: `YYYY-MM-DD`.
+    pub fn date() -> Self {
+        Self::new("9999-99-99")
+    }
+
+    /// An IPv4 address, e.g. `192.168.0.1`.
+    pub fn ipv4() -> Self {
+        Self::new("9##.9##.9##.9##")
+    }
+
+    /// A MAC address, e.g. `00:1a:2b:3c:4d:5e`.
+    pub fn mac() -> Self {
+        Self::new("hh:hh:hh:hh:hh:hh")
+    }
+
+    /// A card number of four groups of four digits.
+    pub fn card() -> Self {
+        Self::new("9999 9999 9999 9999")
+    }
+
+    /// Renders the part of the template starting from a given position,
+    /// showing the slots with a placeholder character.
+    fn render(&self, from: usize) -> String {
+        self.tokens
+            .iter()
+            .skip(from)
+            .map(|token| match token {
+                Token::Literal(chr) => *chr,
+                _ => SLOT_PLACEHOLDER,
+            })
+            .collect()
+    }
+
+    /// Returns the whole template with the slots shown as `_`, e.g. `(___) ___-____`.
+    pub fn placeholder(&self) -> String {
+        self.render(0)
+    }
+
+    /// Returns the part of the template which is not filled yet.
+    pub(crate) fn remaining(&self, value: &[char]) -> String {
+        self.render(self.conform(value, 0).next)
+    }
+
+    /// Checks whether all required slots of the template are filled.
+    pub(crate) fn is_complete(&self, value: &[char]) -> bool {
+        let next = self.conform(value, 0).next;
+        !self.tokens[next..]
+            .iter()
+            .any(|token| token.is_slot() && *token != Token::OptionalDigit)
+    }
+
+    /// Applies the mask to a text: drops the characters which don't fit
+    /// the template and inserts the literals, tracking the cursor position.
+    pub(crate) fn conform(&self, value: &[char], cursor: usize) -> Conformed {
+        let mut out = Conformed {
+            value: vec![],
+            literals: vec![],
+            cursor: 0,
+            next: 0,
+        };
+        // Literals typed explicitly to skip optional slots.
+        let mut skipped = vec![];
+
+        for (i, &chr) in value.iter().enumerate() {
+            let t = out.next;
+            match self.tokens.get(t) {
+                None => {}
+                Some(Token::Literal(literal)) if *literal == chr => {
+                    out.push(chr, true);
+                    skipped.push(false);
+                }
+                Some(_) => {
+                    // The next slot accepting the character, with literals before it.
+                    let slot = self.tokens[t..]
+                        .iter()
+                        .position(Token::is_slot)
+                        .map(|offset| t + offset);
+                    // The literal after the current group if the rest of the group is optional.
+                    let skip_to = self.tokens[t..]
+                        .iter()
+                        .position(|token| !matches!(token, Token::OptionalDigit))
+                        .map(|offset| t + offset)
+                        .filter(|&ix| ix > t && self.tokens[ix] == Token::Literal(chr));
+
+                    if let Some(slot) = slot.filter(|&ix| self.tokens[ix].accepts(chr)) {
+                        for token in &self.tokens[t..slot] {
+                            if let Token::Literal(literal) = token {
+                                out.push(*literal, true);
+                                skipped.push(false);
+                            }
+                        }
+                        out.next = slot;
+                        out.push(chr, false);
+                        skipped.push(false);
+                    } else if let Some(ix) = skip_to {
+                        out.next = ix;
+                        out.push(chr, true);
+                        skipped.push(true);
+                    }
+                }
+            }
+
+            if i + 1 == cursor {
+                out.cursor = out.value.len();
+            }
+        }
+
+        // Trailing literals are inserted with the next typed character
+        // unless they've been typed explicitly to skip optional slots.
+        while out.literals.last() == Some(&true) && skipped.last() == Some(&false) {
+            out.value.pop();
+            out.literals.pop();
+            skipped.pop();
+            out.next -= 1;
+        }
+        out.cursor = out.cursor.min(out.value.len());
+
+        out
+    }
+}
+
+impl Conformed {
+    fn push(&mut self, chr: char, literal: bool) {
+        self.value.push(chr);
+        self.literals.push(literal);
+        self.next += 1;
+    }
+}
+
+impl From<&str> for InputMask {
+    fn from(template: &str) -> Self {
+        Self::new(template)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conform(mask: &InputMask, text: &str) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        mask.conform(&chars, chars.len())
+            .value
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn phone() {
+        let mask = InputMask::new("(999) 999-9999");
+        assert_eq!(conform(&mask, "5"), "(5");
+        assert_eq!(conform(&mask, "555"), "(555");
+        assert_eq!(conform(&mask, "5551"), "(555) 1");
+        assert_eq!(conform(&mask, "(555) "), "(555");
+        assert_eq!(conform(&mask, "555a1234567890"), "(555) 123-4567");
+        assert_eq!(mask.remaining(&['(', '5']), "__) ___-____");
+        assert!(!mask.is_complete(&['(', '5']));
+    }
+
+    #[test]
+    fn ipv4() {
+        let mask = InputMask::ipv4();
+        assert_eq!(conform(&mask, "10."), "10.");
+        assert_eq!(conform(&mask, "10.0.0.1"), "10.0.0.1");
+        assert_eq!(conform(&mask, "1921681"), "192.168.1");
+        assert!(mask.is_complete(&"10.0.0.1".chars().collect::<Vec<_>>()));
+        assert!(!mask.is_complete(&"10.0.0.".chars().collect::<Vec<_>>()));
+    }
+
+    #[test]
+    fn cursor() {
+        let mask = InputMask::date();
+        let chars: Vec<char> = "20240".chars().collect();
+        let conformed = mask.conform(&chars, 5);
+        assert_eq!(conformed.value.iter().collect::<String>(), "2024-0");
+        assert_eq!(conformed.cursor, 6);
+        assert_eq!(
+            conformed.literals,
+            vec![false, false, false, false, true, false]
+        );
+    }
+}
@@ -6,9 +6,13 @@ use std::{
     },
 };
 
-use console::Term;
-
-use crate::{progress::ProgressBar, theme::THEME, ThemeState};
+use crate::{
+    output,
+    progress::{self, ProgressBar},
+    theme::THEME,
+    transcript::{self, Entry},
+    ThemeState,
+};
 
 const HEADER_HEIGHT: usize = 1;
 
@@ -25,12 +29,12 @@ impl MultiProgress {
     /// Creates a new multi-progress bar with a given prompt.
     pub fn new(prompt: impl Display) -> Self {
         let theme = THEME.read().unwrap();
-        let multi = indicatif::MultiProgress::new();
+        let multi = indicatif::MultiProgress::with_draw_target(output::draw_target());
 
         let header =
             theme.format_header(&ThemeState::Active, (prompt.to_string() + "\n ").trim_end());
 
-        multi.println(header).ok();
+        output::println(&multi, &header);
 
         Self {
             multi,
@@ -92,7 +96,11 @@ impl MultiProgress {
         let symbol = theme.remark_symbol();
         let log = theme.format_log_with_spacing(&message.to_string(), &symbol, false);
         self.logs.fetch_add(log.lines().count(), Ordering::SeqCst);
-        self.multi.println(log).ok();
+        output::println(&self.multi, &log);
+        transcript::record(Entry::Log {
+            level: "remark",
+            text: message.to_string(),
+        });
     }
 
     /// Stops the multi-progress bar with a submitted (successful) state.
@@ -111,6 +119,12 @@ impl MultiProgress {
     }
 
     fn stop_with(&self, state: &ThemeState) {
+        let message = match state {
+            ThemeState::Error(error) => format!("{}: {error}", self.prompt),
+            _ => self.prompt.clone(),
+        };
+        progress::record_outcome(&message, state);
+
         let mut inner_height = self.logs.load(Ordering::SeqCst);
 
         // Redraw all progress bars.
@@ -121,7 +135,10 @@ impl MultiProgress {
             pb.bar.finish_and_clear();
         }
 
-        let term = Term::stderr();
+        // A non-interactive output can't be redrawn.
+        let Some(term) = output::term() else {
+            return;
+        };
 
         // Move up to the header, clear and print the new header, then move down.
         term.move_cursor_up(inner_height).ok();
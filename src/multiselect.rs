@@ -4,7 +4,7 @@ use std::{fmt::Display, rc::Rc};
 
 use console::Key;
 
-use crate::view::ListView;
+use crate::view::{grouped_rows, ListView, Row};
 use crate::{
     filter::{FilteredView, LabeledItem},
     prompt::{
@@ -20,21 +20,35 @@ struct Checkbox<T> {
     label: String,
     hint: String,
     selected: bool,
+    group: Option<usize>,
+    disabled: bool,
 }
 
 impl<T> LabeledItem for Checkbox<T> {
     fn label(&self) -> &str {
         &self.label
     }
+
+    fn group(&self) -> Option<usize> {
+        self.group
+    }
 }
 
 /// A prompt that asks for one or more selections from a list of options.
+///
+/// `Space` toggles an item, `a` selects all items, `n` selects none,
+/// and `i` inverts the selection. In the filter mode, the keys are
+/// `Alt-a`, `Alt-n`, and `Alt-i` acting on the filtered items.
 pub struct MultiSelect<T> {
     prompt: String,
     items: Vec<Rc<RefCell<Checkbox<T>>>>,
+    groups: Vec<String>,
+    /// The row under the cursor (an item or a group header).
     cursor: usize,
     initial_values: Option<Vec<T>>,
     required: bool,
+    min_selected: Option<usize>,
+    max_selected: Option<usize>,
     filter: FilteredView<Checkbox<T>>,
     page: ListView,
 }
@@ -48,9 +62,12 @@ where
         Self {
             prompt: prompt.to_string(),
             items: vec![],
+            groups: vec![],
             cursor: 0,
             initial_values: None,
             required: true,
+            min_selected: None,
+            max_selected: None,
             filter: FilteredView::default(),
             page: ListView::default(),
         }
@@ -63,10 +80,22 @@ where
             label: label.to_string(),
             hint: hint.to_string(),
             selected: false,
+            group: None,
+            disabled: false,
         })));
         self
     }
 
+    /// Adds an item which is shown but can't be selected (dimmed and skipped
+    /// by the cursor), with a reason shown as a hint.
+    pub fn disabled_item(self, value: T, label: impl Display, reason: impl Display) -> Self {
+        let select = self.item(value, label, reason);
+        if let Some(item) = select.items.last() {
+            item.borrow_mut().disabled = true;
+        }
+        select
+    }
+
     /// Adds multiple items to the list of options.
     pub fn items(mut self, items: &[(T, impl Display, impl Display)]) -> Self {
         for (value, label, hint) in items {
@@ -75,6 +104,23 @@ where
         self
     }
 
+    /// Adds a group of items under a section header, selecting the header
+    /// toggles the whole group.
+    ///
+    /// The groups are kept together while filtering, the items without
+    /// a group are listed before the groups.
+    pub fn group(mut self, label: impl Display, items: &[(T, impl Display, impl Display)]) -> Self {
+        let group = self.groups.len();
+        self.groups.push(label.to_string());
+
+        let start = self.items.len();
+        self = self.items(items);
+        for item in &self.items[start..] {
+            item.borrow_mut().group = Some(group);
+        }
+        self
+    }
+
     /// Sets the initially selected values.
     pub fn initial_values(mut self, value: Vec<T>) -> Self {
         self.initial_values = Some(value);
@@ -88,6 +134,20 @@ where
         self
     }
 
+    /// Requires at least a given number of selected items on submit
+    /// (unless nothing is selected and the prompt isn't [`required`](MultiSelect::required)).
+    pub fn min_selected(mut self, min: usize) -> Self {
+        self.min_selected = Some(min);
+        self
+    }
+
+    /// Limits the number of selected items: selecting more items is blocked
+    /// with an error, and the footer shows the count (like `2 of 3 max`).
+    pub fn max_selected(mut self, max: usize) -> Self {
+        self.max_selected = Some(max);
+        self
+    }
+
     /// Enables the filter mode ("fuzzy search").
     ///
     /// The filter mode allows to filter the items by typing.
@@ -115,52 +175,184 @@ where
                 "No items added to the list",
             ));
         }
+        if self.items.iter().all(|item| item.borrow().disabled) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "All items in the list are disabled",
+            ));
+        }
+        let enabled = self.items.iter().filter(|i| !i.borrow().disabled).count();
+        match (self.min_selected, self.max_selected) {
+            (Some(min), Some(max)) if min > max => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("min_selected ({min}) is greater than max_selected ({max})"),
+                ));
+            }
+            (_, Some(0)) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "max_selected must be greater than 0",
+                ));
+            }
+            (Some(min), _) if min > enabled => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("min_selected ({min}) is greater than the number of enabled items"),
+                ));
+            }
+            _ => {}
+        }
+        // The items without a group go first (the sort is stable).
+        self.items.sort_by_key(|item| item.borrow().group);
         if let Some(initial_values) = &self.initial_values {
             for item in self.items.iter_mut() {
-                if initial_values.contains(&item.borrow().value) {
+                let enabled = !item.borrow().disabled;
+                if enabled && initial_values.contains(&item.borrow().value) {
                     item.borrow_mut().selected = true;
                 }
             }
         }
         self.filter.set(self.items.to_vec());
+        self.skip_disabled();
         <Self as PromptInteraction<Vec<T>>>::interact(self)
     }
 }
 
+impl<T: Clone> MultiSelect<T> {
+    /// Returns the rows of the filtered items with the group headers.
+    fn rows(&self) -> Vec<Row> {
+        grouped_rows(self.filter.items().iter().map(|item| item.borrow().group))
+    }
+
+    /// Returns `true` if the row can be highlighted: a header or an enabled item.
+    fn is_enabled(&self, rows: &[Row], row: usize) -> bool {
+        match rows.get(row) {
+            Some(&Row::Item(i)) => !self.filter.items()[i].borrow().disabled,
+            Some(Row::Header(_)) => true,
+            None => false,
+        }
+    }
+
+    /// Moves the cursor to the next enabled row (or the previous one
+    /// if `up`), it stays if there is none.
+    fn move_cursor(&mut self, up: bool) {
+        let rows = self.rows();
+        let next = match up {
+            true => (0..self.cursor).rev().find(|&i| self.is_enabled(&rows, i)),
+            false => (self.cursor + 1..rows.len()).find(|&i| self.is_enabled(&rows, i)),
+        };
+        self.cursor = next.unwrap_or(self.cursor);
+        self.page.scroll_to(self.cursor);
+    }
+
+    /// Moves the cursor off a disabled item to the closest enabled row below
+    /// (or above if there is none).
+    fn skip_disabled(&mut self) {
+        let rows = self.rows();
+        if !self.is_enabled(&rows, self.cursor) {
+            let below = (self.cursor..rows.len()).find(|&i| self.is_enabled(&rows, i));
+            let above = (0..self.cursor).rev().find(|&i| self.is_enabled(&rows, i));
+            self.cursor = below.or(above).unwrap_or(self.cursor);
+        }
+        self.page.scroll_to(self.cursor);
+    }
+
+    /// Toggles the item or the whole group (of the filtered items) under
+    /// the cursor: the group is selected unless all its items are selected.
+    fn toggle(&self) -> State<Vec<T>> {
+        match self.rows().get(self.cursor) {
+            Some(&Row::Item(i)) => {
+                let item = self.filter.items()[i].clone();
+                self.select(&[item], |selected| !selected)
+            }
+            Some(&Row::Header(group)) => {
+                let items = self.filter.items().iter();
+                let items: Vec<_> = items
+                    .filter(|i| i.borrow().group == Some(group) && !i.borrow().disabled)
+                    .cloned()
+                    .collect();
+                let selected = !items.iter().all(|item| item.borrow().selected);
+                self.select(&items, |_| selected)
+            }
+            None => State::Active,
+        }
+    }
+
+    /// Changes the selection of given items skipping the disabled ones,
+    /// unless more items than the maximum would be selected.
+    fn select(
+        &self,
+        items: &[Rc<RefCell<Checkbox<T>>>],
+        toggle: impl Fn(bool) -> bool,
+    ) -> State<Vec<T>> {
+        let items: Vec<_> = items.iter().filter(|i| !i.borrow().disabled).collect();
+        let selected = self.selected_count();
+        let count = items
+            .iter()
+            .map(|item| item.borrow().selected)
+            .fold(selected, |count, was| match (was, toggle(was)) {
+                (false, true) => count + 1,
+                (true, false) => count - 1,
+                _ => count,
+            });
+        if let Some(max) = self.max_selected {
+            // Deselecting is allowed when over the limit (by the initial values).
+            if count > max && count > selected {
+                let s = if max == 1 { "" } else { "s" };
+                return State::Error(format!("You can select at most {max} item{s}"));
+            }
+        }
+
+        for item in items {
+            let mut item = item.borrow_mut();
+            item.selected = toggle(item.selected);
+        }
+        State::Active
+    }
+
+    /// Returns the number of selected items (including the filtered out ones).
+    fn selected_count(&self) -> usize {
+        self.items.iter().filter(|i| i.borrow().selected).count()
+    }
+}
+
 impl<T: Clone> PromptInteraction<Vec<T>> for MultiSelect<T> {
     fn on(&mut self, event: &Event) -> State<Vec<T>> {
         let Event::Key(key) = event;
 
+        // The letters are typed into the filter input in the filter mode.
+        let letter = match key {
+            Key::UnknownEscSeq(chars) if chars.len() == 1 => Some(chars[0]),
+            Key::Char(chr) if self.filter.input().is_none() => Some(*chr),
+            _ => None,
+        };
+        let toggle: Option<fn(bool) -> bool> = match letter {
+            Some('a') => Some(|_| true),
+            Some('n') => Some(|_| false),
+            Some('i') => Some(|selected| !selected),
+            _ => None,
+        };
+        if let Some(toggle) = toggle {
+            return self.select(self.filter.items(), toggle);
+        }
+
         if let Some(state) = self.filter.on(key, self.items.clone()) {
-            if self.filter.items().is_empty() || self.cursor > self.filter.items().len() - 1 {
+            if self.cursor >= self.rows().len() {
                 self.cursor = 0;
             }
+            self.skip_disabled();
             return state;
         }
 
         match key {
             Key::ArrowLeft | Key::ArrowUp | Key::Char('k') | Key::Char('h') => {
-                if self.cursor > 0 {
-                    self.cursor -= 1;
-                }
-
-                if self.cursor < self.page.start {
-                    self.page.start = self.cursor;
-                }
+                self.move_cursor(true);
             }
             Key::ArrowRight | Key::ArrowDown | Key::Char('j') | Key::Char('l') => {
-                if !self.filter.items().is_empty() && self.cursor < self.filter.items().len() - 1 {
-                    self.cursor += 1;
-                }
-
-                if self.cursor >= self.page.start + self.page.height {
-                    self.page.start = self.cursor - self.page.height + 1;
-                }
-            }
-            Key::Char(' ') => {
-                let mut item = self.filter.items()[self.cursor].borrow_mut();
-                item.selected = !item.selected;
+                self.move_cursor(false);
             }
+            Key::Char(' ') => return self.toggle(),
             Key::Enter => {
                 let selected_items = self
                     .items
@@ -174,6 +366,14 @@ impl<T: Clone> PromptInteraction<Vec<T>> for MultiSelect<T> {
                     return State::Error("Input required".to_string());
                 }
 
+                if let Some(min) = self.min_selected {
+                    // An optional prompt can be submitted empty anyway.
+                    if !selected_items.is_empty() && selected_items.len() < min {
+                        let s = if min == 1 { "" } else { "s" };
+                        return State::Error(format!("Select at least {min} item{s}"));
+                    }
+                }
+
                 return State::Submit(selected_items);
             }
             _ => {}
@@ -206,24 +406,57 @@ impl<T: Clone> PromptInteraction<Vec<T>> for MultiSelect<T> {
             _ => self.filter.items(),
         };
 
+        let rows = grouped_rows(items_to_render.iter().map(|item| item.borrow().group));
         let mut items_render = String::new();
-        for (i, item) in items_to_render
-            .iter()
-            .map(|i| i.borrow())
+        for (i, row) in rows
+            .into_iter()
             .enumerate()
             .skip(self.page.start)
             .take(self.page.height)
         {
-            items_render.push_str(&theme.format_multiselect_item(
-                &state.into(),
-                item.selected,
-                i == self.cursor,
-                &item.label,
-                &item.hint,
-            ));
+            items_render.push_str(&match row {
+                Row::Header(group) => {
+                    // The group is checked if all its enabled items are selected
+                    // (and there is at least one).
+                    let mut items = items_to_render
+                        .iter()
+                        .map(|item| item.borrow())
+                        .filter(|item| item.group == Some(group) && !item.disabled)
+                        .peekable();
+                    let checked = items.peek().is_some() && items.all(|item| item.selected);
+                    theme.format_multiselect_group(
+                        &state.into(),
+                        &self.groups[group],
+                        checked,
+                        i == self.cursor,
+                    )
+                }
+                Row::Item(ix) if items_to_render[ix].borrow().disabled => {
+                    let item = items_to_render[ix].borrow();
+                    theme.format_multiselect_disabled_item(&state.into(), &item.label, &item.hint)
+                }
+                Row::Item(ix) => {
+                    let item = items_to_render[ix].borrow();
+                    theme.format_multiselect_item(
+                        &state.into(),
+                        item.selected,
+                        i == self.cursor,
+                        &item.label,
+                        &item.hint,
+                    )
+                }
+            });
         }
 
-        let not_rendered_items = self.items.iter().filter(|i| i.borrow().selected).count()
+        let selected_items = self.selected_count();
+        let count = match self.max_selected {
+            Some(max) => format!("{selected_items} of {max} max"),
+            None => {
+                let enabled = self.items.iter().filter(|i| !i.borrow().disabled);
+                format!("{selected_items} of {} selected", enabled.count())
+            }
+        };
+        let not_rendered_items = selected_items
             - self
                 .filter
                 .items()
@@ -235,11 +468,10 @@ impl<T: Clone> PromptInteraction<Vec<T>> for MultiSelect<T> {
         let footer = if not_rendered_items > 0 {
             theme.format_footer_with_message(
                 &state.into(),
-                &format!(
-                    "{not_rendered_items} selected item{s} not displayed",
-                    s = if not_rendered_items > 1 { "s" } else { "" }
-                ),
+                &format!("{count}, {not_rendered_items} not displayed"),
             )
+        } else if selected_items > 0 || self.max_selected.is_some() {
+            theme.format_footer_with_message(&state.into(), &count)
         } else {
             theme.format_footer(&state.into())
         };
@@ -251,6 +483,17 @@ impl<T: Clone> PromptInteraction<Vec<T>> for MultiSelect<T> {
     fn input(&mut self) -> Option<&mut StringCursor> {
         self.filter.input()
     }
+
+    fn transcript(&self) -> Option<(String, String)> {
+        let labels = self
+            .items
+            .iter()
+            .map(|item| item.borrow())
+            .filter(|item| item.selected)
+            .map(|item| item.label.clone())
+            .collect::<Vec<_>>();
+        Some((self.prompt.clone(), labels.join(", ")))
+    }
 }
 
 #[cfg(test)]
@@ -266,4 +509,181 @@ mod test {
             result.unwrap_err().to_string()
         );
     }
+
+    #[test]
+    fn toggle_group() {
+        let mut select = MultiSelect::new("Templates")
+            .item("empty", "Empty", "")
+            .group("Frontend", &[("react", "React", ""), ("vue", "Vue", "")]);
+        select.filter.set(select.items.to_vec());
+
+        select.on(&Event::Key(Key::ArrowDown));
+        select.on(&Event::Key(Key::ArrowDown));
+        select.on(&Event::Key(Key::ArrowDown));
+        select.on(&Event::Key(Key::Char(' ')));
+        // The header selects the rest of the group, then deselects it.
+        select.on(&Event::Key(Key::ArrowUp));
+        select.on(&Event::Key(Key::ArrowUp));
+        select.on(&Event::Key(Key::Char(' ')));
+        match select.on(&Event::Key(Key::Enter)) {
+            State::Submit(values) => assert_eq!(values, ["react", "vue"]),
+            _ => panic!("expected a submit"),
+        }
+        select.on(&Event::Key(Key::Char(' ')));
+        assert!(select.items.iter().all(|item| !item.borrow().selected));
+    }
+
+    #[test]
+    fn select_all_filtered() {
+        let mut select = MultiSelect::new("Crates")
+            .items(&[
+                ("core", "core", ""),
+                ("cli", "cli", ""),
+                ("book", "book", ""),
+            ])
+            .filter_mode();
+        select.filter.set(select.items.to_vec());
+
+        let alt = |chr| Event::Key(Key::UnknownEscSeq(vec![chr]));
+        select.on(&alt('a'));
+        select.on(&alt('i'));
+        assert!(select.items.iter().all(|item| !item.borrow().selected));
+
+        select.filter.input().unwrap().extend("c");
+        select.on(&Event::Key(Key::Char('c')));
+        assert_eq!(select.filter.items().len(), 2);
+        select.on(&alt('a'));
+        match select.on(&Event::Key(Key::Enter)) {
+            State::Submit(values) => assert_eq!(values, ["core", "cli"]),
+            _ => panic!("expected a submit"),
+        }
+    }
+
+    #[test]
+    fn selection_limits() {
+        let mut select = MultiSelect::new("Reviewers")
+            .items(&[("a", "Alice", ""), ("b", "Bob", ""), ("c", "Carol", "")])
+            .min_selected(2)
+            .max_selected(2);
+        select.filter.set(select.items.to_vec());
+
+        select.on(&Event::Key(Key::Char(' ')));
+        match select.on(&Event::Key(Key::Enter)) {
+            State::Error(err) => assert_eq!(err, "Select at least 2 items"),
+            _ => panic!("expected an error"),
+        }
+        match select.on(&Event::Key(Key::Char('a'))) {
+            State::Error(err) => assert_eq!(err, "You can select at most 2 items"),
+            _ => panic!("expected an error"),
+        }
+        select.on(&Event::Key(Key::ArrowDown));
+        select.on(&Event::Key(Key::Char(' ')));
+        select.on(&Event::Key(Key::ArrowDown));
+        assert!(matches!(
+            select.on(&Event::Key(Key::Char(' '))),
+            State::Error(_)
+        ));
+        let frame = console::strip_ansi_codes(&select.render(&State::Active)).to_string();
+        assert!(frame.contains("2 of 2 max"));
+        match select.on(&Event::Key(Key::Enter)) {
+            State::Submit(values) => assert_eq!(values, ["a", "b"]),
+            _ => panic!("expected a submit"),
+        }
+    }
+
+    #[test]
+    fn all_disabled() {
+        let mut select = MultiSelect::new("Services")
+            .disabled_item("postgres", "PostgreSQL", "requires docker")
+            .disabled_item("rabbit", "RabbitMQ", "requires docker");
+        let err = select.interact().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(err.to_string(), "All items in the list are disabled");
+    }
+
+    #[test]
+    fn disabled_group() {
+        let mut select = MultiSelect::new("Services")
+            .item("redis", "Redis", "")
+            .group("Queues", &[("kafka", "Kafka", ""), ("nats", "NATS", "")]);
+        for item in &select.items[1..] {
+            item.borrow_mut().disabled = true;
+        }
+        select.filter.set(select.items.to_vec());
+        select.on(&Event::Key(Key::Char(' ')));
+
+        // A group without enabled items isn't checked.
+        let frame = console::strip_ansi_codes(&select.render(&State::Active)).to_string();
+        let header = frame.lines().find(|line| line.contains("Queues")).unwrap();
+        assert!(!header.contains('◼') && !header.contains("[+]"));
+    }
+
+    #[test]
+    fn invalid_limits() {
+        let items = [("a", "Alice", ""), ("b", "Bob", "")];
+        let mut select = MultiSelect::new("Reviewers")
+            .items(&items)
+            .min_selected(2)
+            .max_selected(1);
+        let err = select.interact().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        let mut select = MultiSelect::new("Reviewers")
+            .items(&items)
+            .disabled_item("c", "Carol", "away")
+            .min_selected(3);
+        let err = select.interact().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        let mut select = MultiSelect::new("Reviewers").items(&items).max_selected(0);
+        let err = select.interact().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn optional_min_selected() {
+        let mut select = MultiSelect::new("Reviewers")
+            .items(&[("a", "Alice", ""), ("b", "Bob", "")])
+            .required(false)
+            .min_selected(2);
+        select.filter.set(select.items.to_vec());
+
+        match select.on(&Event::Key(Key::Enter)) {
+            State::Submit(values) => assert!(values.is_empty()),
+            _ => panic!("expected an empty submit"),
+        }
+        select.on(&Event::Key(Key::Char(' ')));
+        assert!(matches!(
+            select.on(&Event::Key(Key::Enter)),
+            State::Error(_)
+        ));
+    }
+
+    #[test]
+    fn disabled_items() {
+        let mut select = MultiSelect::new("Services")
+            .disabled_item("postgres", "PostgreSQL", "requires docker")
+            .item("redis", "Redis", "")
+            .group("Queues", &[("kafka", "Kafka", ""), ("nats", "NATS", "")])
+            .disabled_item("rabbit", "RabbitMQ", "requires docker");
+        select.items.sort_by_key(|item| item.borrow().group);
+        select.filter.set(select.items.to_vec());
+        select.skip_disabled();
+        assert_eq!(select.cursor, 1);
+
+        select.on(&Event::Key(Key::ArrowUp));
+        assert_eq!(select.cursor, 1);
+        select.on(&Event::Key(Key::Char('a')));
+        select.on(&Event::Key(Key::ArrowDown));
+        select.on(&Event::Key(Key::Char(' ')));
+        select.on(&Event::Key(Key::Char(' ')));
+        match select.on(&Event::Key(Key::Enter)) {
+            State::Submit(values) => assert_eq!(values, ["redis", "kafka", "nats"]),
+            _ => panic!("expected a submit"),
+        }
+
+        let frame = console::strip_ansi_codes(&select.render(&State::Active)).to_string();
+        assert!(frame.contains("RabbitMQ (requires docker)"));
+        assert!(frame.contains("3 of 3 selected"));
+    }
 }
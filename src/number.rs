@@ -0,0 +1,413 @@
+use std::fmt::Display;
+use std::io;
+use std::str::FromStr;
+
+use console::Key;
+
+use crate::{
+    prompt::{
+        cursor::StringCursor,
+        interaction::{Event, PromptInteraction, State},
+    },
+    theme::THEME,
+};
+
+/// The number of steps to change the value by `PageUp` and `PageDown`.
+const PAGE_STEPS: i32 = 10;
+
+/// Returns the number of decimal places in the shortest representation of the value.
+fn decimals(value: impl Display) -> usize {
+    value
+        .to_string()
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len())
+}
+
+/// A numeric type accepted by the [`Number`] prompt.
+///
+/// It's implemented for all primitive integer and floating point types.
+/// [`Default`] is expected to be zero.
+pub trait Numeric: Copy + Default + PartialOrd + FromStr + Display {
+    /// The default step of the `Up` and `Down` keys.
+    const ONE: Self;
+
+    /// The smallest value of the type.
+    const MIN: Self;
+
+    /// Changes the value by a given number of steps, saturating at the bounds
+    /// of the type.
+    fn add_steps(self, step: Self, steps: i32) -> Self;
+}
+
+macro_rules! impl_numeric_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl Numeric for $ty {
+                const ONE: Self = 1;
+                const MIN: Self = <$ty>::MIN;
+
+                fn add_steps(self, step: Self, steps: i32) -> Self {
+                    let delta = step.saturating_mul(steps.unsigned_abs() as Self);
+                    if steps < 0 {
+                        self.saturating_sub(delta)
+                    } else {
+                        self.saturating_add(delta)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_numeric_float {
+    ($($ty:ty),*) => {
+        $(
+            impl Numeric for $ty {
+                const ONE: Self = 1.0;
+                const MIN: Self = <$ty>::MIN;
+
+                fn add_steps(self, step: Self, steps: i32) -> Self {
+                    self + step * steps as Self
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_numeric_float!(f32, f64);
+
+/// A prompt that accepts a number in a given range.
+///
+/// * `Up`/`Down` increment/decrement the value by the step.
+/// * `PageUp`/`PageDown` change the value by 10 steps.
+/// * The value is clamped to the range when changed by the keys, and
+///   the range is checked on submit.
+///
+/// The number can be of any [`Numeric`] type, like integers or floats.
+/// The range, the step, and the default value are of the same type.
+///
+/// # Example
+///
+/// ```
+/// use cliclack::Number;
+///
+/// # fn test() -> std::io::Result<()> {
+/// let port: u16 = Number::new("Port")
+///     .min(1)
+///     .max(65535)
+///     .default_value(8080)
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
+pub struct Number<T> {
+    prompt: String,
+    input: StringCursor,
+    placeholder: StringCursor,
+    min: Option<T>,
+    max: Option<T>,
+    step: T,
+    precision: Option<usize>,
+    default: Option<T>,
+}
+
+impl<T: Numeric> Number<T> {
+    /// Creates a new number prompt.
+    pub fn new(prompt: impl Display) -> Self {
+        Self {
+            prompt: prompt.to_string(),
+            input: StringCursor::default(),
+            placeholder: StringCursor::default(),
+            min: None,
+            max: None,
+            step: T::ONE,
+            precision: None,
+            default: None,
+        }
+    }
+
+    /// Sets the minimum allowed value (inclusive).
+    pub fn min(mut self, min: T) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Sets the maximum allowed value (inclusive).
+    pub fn max(mut self, max: T) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Sets the (positive) increment of the `Up` and `Down` keys. Default: `1`.
+    pub fn step(mut self, step: T) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Sets the number of decimal places of a floating point value.
+    ///
+    /// By default, it's the number of decimal places of the step,
+    /// e.g. `0` for the step `1`, and `2` for the step `0.25`.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Sets the default value which is used if no value is supplied,
+    /// and which is shown as a hint (placeholder) if one is not already set.
+    pub fn default_value(mut self, value: T) -> Self {
+        self.default = Some(value);
+        self
+    }
+
+    /// Sets the placeholder (hint) text for the input.
+    pub fn placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder.extend(placeholder);
+        self
+    }
+
+    /// Starts the prompt interaction.
+    pub fn interact(&mut self) -> io::Result<T> {
+        if self.placeholder.is_empty() {
+            if let Some(default) = self.default {
+                self.placeholder.extend(&self.format(default));
+                self.placeholder.extend(" (default)");
+            }
+        }
+        <Self as PromptInteraction<T>>::interact(self)
+    }
+
+    fn precision_or_default(&self) -> usize {
+        self.precision.unwrap_or_else(|| decimals(self.step))
+    }
+
+    fn format(&self, value: T) -> String {
+        // The precision is ignored by integers.
+        format!("{value:.*}", self.precision_or_default())
+    }
+
+    fn clamp(&self, value: T) -> T {
+        match (self.min, self.max) {
+            (Some(min), _) if value < min => min,
+            (_, Some(max)) if value > max => max,
+            _ => value,
+        }
+    }
+
+    /// Returns a description of the range, e.g. `between 1 and 10`.
+    fn range(&self) -> Option<String> {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => Some(format!(
+                "between {} and {}",
+                self.format(min),
+                self.format(max)
+            )),
+            (Some(min), None) => Some(format!("at least {}", self.format(min))),
+            (None, Some(max)) => Some(format!("at most {}", self.format(max))),
+            (None, None) => None,
+        }
+    }
+
+    /// Changes the value by a given number of steps, clamping it to the range.
+    fn increment(&mut self, steps: i32) {
+        let value = match self.input.to_string().trim().parse::<T>() {
+            Ok(value) => self.clamp(value.add_steps(self.step, steps)),
+            // Start from the default value or the range bound.
+            Err(_) => self.clamp(self.default.or(self.min).unwrap_or_default()),
+        };
+
+        self.input.clear();
+        self.input.extend(&self.format(value));
+        self.input.move_end();
+    }
+
+    /// Checks whether a typed character can be a part of the number.
+    fn accepts(&self, chr: char) -> bool {
+        match chr {
+            '0'..='9' => true,
+            '-' => self.min.unwrap_or(T::MIN) < T::default(),
+            '.' => self.precision_or_default() > 0,
+            _ => false,
+        }
+    }
+
+    /// Parses and checks the value against the range and the precision.
+    fn value(&self) -> Result<T, String> {
+        let input = self.input.to_string();
+        let value = input
+            .trim()
+            .parse::<T>()
+            .map_err(|_| "Not a number".to_string())?;
+
+        let out_of_range =
+            self.min.is_some_and(|min| value < min) || self.max.is_some_and(|max| value > max);
+        if out_of_range {
+            let range = self.range().unwrap_or_default();
+            return Err(format!("The value must be {range}"));
+        }
+
+        let precision = self.precision_or_default();
+        if decimals(value) > precision {
+            return Err(match precision {
+                0 => "The value must be a whole number".to_string(),
+                1 => "The value must have at most 1 decimal place".to_string(),
+                _ => format!("The value must have at most {precision} decimal places"),
+            });
+        }
+
+        Ok(value)
+    }
+}
+
+impl<T: Numeric> PromptInteraction<T> for Number<T> {
+    fn on(&mut self, event: &Event) -> State<T> {
+        let Event::Key(key) = event;
+
+        match key {
+            Key::ArrowUp => self.increment(1),
+            Key::ArrowDown => self.increment(-1),
+            Key::PageUp => self.increment(PAGE_STEPS),
+            Key::PageDown => self.increment(-PAGE_STEPS),
+            Key::Char(chr) if !chr.is_ascii_control() && !self.accepts(*chr) => {
+                // Reject the character which has just been typed in.
+                self.input.delete_left();
+            }
+            Key::Enter => {
+                if self.input.is_empty() {
+                    match self.default {
+                        Some(default) => self.input.extend(&self.format(default)),
+                        None => return State::Error("Input required".to_string()),
+                    }
+                }
+
+                return match self.value() {
+                    Ok(value) => State::Submit(value),
+                    Err(err) => State::Error(err),
+                };
+            }
+            _ => {}
+        }
+
+        State::Active
+    }
+
+    fn render(&mut self, state: &State<T>) -> String {
+        let theme = THEME.read().unwrap();
+
+        let header = theme.format_header(&state.into(), &self.prompt);
+        let input = if self.input.is_empty() {
+            theme.format_placeholder(&state.into(), &self.placeholder)
+        } else {
+            theme.format_input(&state.into(), &self.input)
+        };
+
+        let hint = match self.range() {
+            Some(range) => format!("{range} (↑/↓ to change)"),
+            None => "(↑/↓ to change)".to_string(),
+        };
+        let footer = theme.format_footer_with_message(&state.into(), &hint);
+
+        header + &input + &footer
+    }
+
+    fn input(&mut self) -> Option<&mut StringCursor> {
+        Some(&mut self.input)
+    }
+
+    fn transcript(&self) -> Option<(String, String)> {
+        Some((self.prompt.clone(), self.input.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press<T: Numeric>(number: &mut Number<T>, key: Key) -> State<T> {
+        number.on(&Event::Key(key))
+    }
+
+    fn type_in<T: Numeric>(number: &mut Number<T>, text: &str) -> State<T> {
+        let mut state = State::Active;
+        for chr in text.chars() {
+            number.input.insert(chr);
+            state = press(number, Key::Char(chr));
+        }
+        state
+    }
+
+    #[test]
+    fn increment_and_clamp() {
+        let mut number = Number::new("Replicas").min(1).max(5).step(2);
+        press(&mut number, Key::ArrowUp);
+        assert_eq!(number.input.to_string(), "1");
+        press(&mut number, Key::ArrowUp);
+        press(&mut number, Key::ArrowUp);
+        assert_eq!(number.input.to_string(), "5");
+        press(&mut number, Key::ArrowUp);
+        assert_eq!(number.input.to_string(), "5");
+        press(&mut number, Key::PageDown);
+        assert_eq!(number.input.to_string(), "1");
+    }
+
+    #[test]
+    fn precision() {
+        let mut number = Number::<f64>::new("Timeout").step(0.25);
+        type_in(&mut number, "1.5");
+        press(&mut number, Key::ArrowUp);
+        assert_eq!(number.input.to_string(), "1.75");
+
+        let mut number = Number::<u16>::new("Port");
+        type_in(&mut number, "8o80");
+        assert_eq!(number.input.to_string(), "880");
+        type_in(&mut number, ".");
+        assert_eq!(number.input.to_string(), "880");
+    }
+
+    #[test]
+    fn range_errors() {
+        let mut number = Number::<u16>::new("Port").min(1).max(100);
+        type_in(&mut number, "200");
+        match press(&mut number, Key::Enter) {
+            State::Error(err) => assert_eq!(err, "The value must be between 1 and 100"),
+            _ => panic!("expected a range error"),
+        }
+
+        let mut number = Number::<f64>::new("Ratio").precision(1);
+        type_in(&mut number, "0.25");
+        match press(&mut number, Key::Enter) {
+            State::Error(err) => assert_eq!(err, "The value must have at most 1 decimal place"),
+            _ => panic!("expected a precision error"),
+        }
+    }
+
+    #[test]
+    fn default_value() {
+        let mut number = Number::<u16>::new("Port").default_value(8080);
+        match press(&mut number, Key::Enter) {
+            State::Submit(value) => assert_eq!(value, 8080),
+            _ => panic!("expected the default value"),
+        }
+    }
+
+    #[test]
+    fn integer_bounds() {
+        let mut number = Number::<u8>::new("Percent").step(100);
+        type_in(&mut number, "-");
+        assert_eq!(number.input.to_string(), "");
+        type_in(&mut number, "200");
+        press(&mut number, Key::ArrowUp);
+        assert_eq!(number.input.to_string(), "255");
+        press(&mut number, Key::PageDown);
+        assert_eq!(number.input.to_string(), "0");
+
+        let mut number = Number::<i64>::new("Offset").min(-5);
+        type_in(&mut number, "-3");
+        match press(&mut number, Key::Enter) {
+            State::Submit(value) => assert_eq!(value, -3),
+            _ => panic!("expected a value"),
+        }
+    }
+}
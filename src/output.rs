@@ -0,0 +1,129 @@
+use std::io::{self, Write};
+use std::sync::Mutex;
+
+use console::Term;
+use indicatif::ProgressDrawTarget;
+use once_cell::sync::Lazy;
+
+/// The refresh rate of progress bars drawn to a terminal (the same as
+/// the `indicatif` default).
+const REFRESH_RATE: u8 = 20;
+
+/// Defines where prompts, messages ([`intro`](crate::intro), [`log`](crate::log),
+/// [`note`](crate::note), etc.) and progress bars are written to.
+///
+/// By default, everything goes to [`Output::Stderr`], so the standard output
+/// stays clean for the data produced by the application.
+///
+/// ```
+/// use cliclack::{set_output, Output};
+///
+/// set_output(Output::Stdout);
+/// ```
+pub enum Output {
+    /// The standard output.
+    Stdout,
+    /// The standard error output (default).
+    Stderr,
+    /// An arbitrary terminal, e.g. [`Term::buffered_stderr`].
+    Term(Term),
+    /// A non-interactive sink, e.g. a file or an in-memory buffer.
+    ///
+    /// Prompts can't interact with the user via a sink and fail with
+    /// [`io::ErrorKind::NotConnected`], and progress bars only print
+    /// their final state.
+    Writer(Box<dyn Write + Send>),
+}
+
+impl Output {
+    /// Creates a non-interactive output into a given writer.
+    pub fn writer(writer: impl Write + Send + 'static) -> Self {
+        Self::Writer(Box::new(writer))
+    }
+
+    fn term(&self) -> Option<Term> {
+        match self {
+            Self::Stdout => Some(Term::stdout()),
+            Self::Stderr => Some(Term::stderr()),
+            Self::Term(term) => Some(term.clone()),
+            Self::Writer(_) => None,
+        }
+    }
+}
+
+/// The global output target (singleton).
+///
+/// It can be set with [`set_output`] function.
+static OUTPUT: Lazy<Mutex<Output>> = Lazy::new(|| Mutex::new(Output::Stderr));
+
+/// Sets the global output target, which is used by all prompts, messages,
+/// and progress bars.
+///
+/// See [`reset_output`] for returning to the default output (stderr).
+pub fn set_output(output: Output) {
+    *OUTPUT.lock().unwrap() = output;
+}
+
+/// Resets the global output target to the default one (stderr).
+pub fn reset_output() {
+    *OUTPUT.lock().unwrap() = Output::Stderr;
+}
+
+/// Returns the terminal of the output target, or `None` if the output is
+/// a non-interactive sink.
+pub(crate) fn term() -> Option<Term> {
+    OUTPUT.lock().unwrap().term()
+}
+
+/// Returns `true` if the output is a non-interactive sink rather than a terminal.
+pub(crate) fn is_writer() -> bool {
+    matches!(*OUTPUT.lock().unwrap(), Output::Writer(_))
+}
+
+/// Writes the text into the output target.
+pub(crate) fn write_str(text: &str) -> io::Result<()> {
+    match &mut *OUTPUT.lock().unwrap() {
+        Output::Writer(writer) => {
+            writer.write_all(text.as_bytes())?;
+            writer.flush()
+        }
+        output => output.term().unwrap().write_str(text),
+    }
+}
+
+/// Returns the draw target for progress bars corresponding to the output.
+pub(crate) fn draw_target() -> ProgressDrawTarget {
+    match &*OUTPUT.lock().unwrap() {
+        Output::Stdout => ProgressDrawTarget::stdout(),
+        Output::Stderr => ProgressDrawTarget::stderr(),
+        Output::Term(term) => ProgressDrawTarget::term(term.clone(), REFRESH_RATE),
+        Output::Writer(_) => ProgressDrawTarget::hidden(),
+    }
+}
+
+/// A progress bar object which is able to print a line above itself.
+pub(crate) trait PrintLine {
+    fn print_line(&self, line: &str);
+}
+
+impl PrintLine for indicatif::ProgressBar {
+    fn print_line(&self, line: &str) {
+        self.println(line);
+    }
+}
+
+impl PrintLine for indicatif::MultiProgress {
+    fn print_line(&self, line: &str) {
+        self.println(line).ok();
+    }
+}
+
+/// Prints a line above the progress bar, or directly into the non-interactive
+/// sink where progress bars are hidden.
+pub(crate) fn println(bar: &impl PrintLine, line: &str) {
+    if is_writer() {
+        write_str(&format!("{line}\n")).ok();
+    } else {
+        bar.print_line(line);
+    }
+}
@@ -8,13 +8,96 @@ use crate::{
         cursor::StringCursor,
         interaction::{Event, PromptInteraction, State},
     },
+    secret::SecretString,
     theme::THEME,
     validate::Validate,
 };
 
 type ValidationCallback = Box<dyn Fn(&String) -> Result<(), String>>;
+type StrengthCallback = Box<dyn Fn(&str) -> Strength>;
+
+/// The strength of a password shown as a meter under the input
+/// (see [`Password::strength`]).
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Strength {
+    /// Trivial to guess.
+    VeryWeak,
+    /// Easy to guess.
+    Weak,
+    /// Acceptable for the low-value accounts.
+    Fair,
+    /// Hard to guess.
+    Strong,
+    /// Practically impossible to guess.
+    VeryStrong,
+}
+
+impl Strength {
+    /// Estimates the strength roughly by the entropy of a random password
+    /// of the same length and character classes (lowercase and uppercase
+    /// letters, digits, and symbols).
+    ///
+    /// It doesn't detect dictionary words or patterns, plug in a dedicated
+    /// estimator for that.
+    pub fn estimate(password: &str) -> Self {
+        let has = |class: fn(&char) -> bool| password.chars().any(|c| class(&c));
+        let pool = [
+            (has(char::is_ascii_lowercase), 26.0),
+            (has(char::is_ascii_uppercase), 26.0),
+            (has(char::is_ascii_digit), 10.0),
+            (has(|c| !c.is_ascii_alphanumeric()), 33.0),
+        ]
+        .iter()
+        .filter(|(present, _)| *present)
+        .map(|(_, size)| size)
+        .sum::<f64>();
+        let bits = password.chars().count() as f64 * pool.max(1.0).log2();
+
+        match bits {
+            bits if bits < 28.0 => Self::VeryWeak,
+            bits if bits < 36.0 => Self::Weak,
+            bits if bits < 60.0 => Self::Fair,
+            bits if bits < 128.0 => Self::Strong,
+            _ => Self::VeryStrong,
+        }
+    }
+
+    /// Returns the level of the strength from 0 (very weak) to 4 (very strong).
+    pub fn level(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl Display for Strength {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            Self::VeryWeak => "Very weak",
+            Self::Weak => "Weak",
+            Self::Fair => "Fair",
+            Self::Strong => "Strong",
+            Self::VeryStrong => "Very strong",
+        };
+        f.write_str(label)
+    }
+}
 
 /// A prompt that masks the input.
+///
+/// # Example
+///
+/// ```
+/// use cliclack::{Password, Strength};
+///
+/// # fn test() -> std::io::Result<()> {
+/// let password = Password::new("New password")
+///     .confirm("Repeat the password")
+///     .strength(Strength::estimate)
+///     .allow_reveal()
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
 #[derive(Default)]
 pub struct Password {
     prompt: String,
@@ -23,6 +106,13 @@ pub struct Password {
     allow_empty: bool,
     validate_on_enter: Option<ValidationCallback>,
     validate_interactively: Option<ValidationCallback>,
+    /// The prompt of the confirmation step.
+    confirm: Option<String>,
+    /// The password entered at the first step while it's being confirmed.
+    entered: Option<StringCursor>,
+    strength: Option<StrengthCallback>,
+    allow_reveal: bool,
+    revealed: bool,
 }
 
 impl Password {
@@ -47,6 +137,30 @@ impl Password {
         self
     }
 
+    /// Asks to repeat the password with a given prompt (e.g. "Repeat password")
+    /// after it's entered and validated. Both are asked again if they differ.
+    pub fn confirm(mut self, prompt: impl Display) -> Self {
+        self.confirm = Some(prompt.to_string());
+        self
+    }
+
+    /// Shows the strength of the password as a meter under the input
+    /// while typing, e.g. with [`Strength::estimate`] or a custom estimator.
+    pub fn strength<F>(mut self, estimator: F) -> Self
+    where
+        F: Fn(&str) -> Strength + 'static,
+    {
+        self.strength = Some(Box::new(estimator));
+        self
+    }
+
+    /// Allows revealing the password temporarily with `Ctrl-R`
+    /// (pressing it again hides the password).
+    pub fn allow_reveal(mut self) -> Self {
+        self.allow_reveal = true;
+        self
+    }
+
     /// Sets a validation callback for the input that is called when the user submits.
     /// The same as [`Password::validate_on_enter`].
     pub fn validate<V>(mut self, validator: V) -> Self
@@ -82,12 +196,14 @@ impl Password {
     }
 
     /// Starts the prompt interaction.
-    pub fn interact(&mut self) -> io::Result<String> {
-        <Self as PromptInteraction<String>>::interact(self)
+    ///
+    /// Returns the password as a [`SecretString`] which is zeroed in memory on drop.
+    pub fn interact(&mut self) -> io::Result<SecretString> {
+        <Self as PromptInteraction<SecretString>>::interact(self)
     }
 }
 
-impl PromptInteraction<String> for Password {
+impl PromptInteraction<SecretString> for Password {
     fn input(&mut self) -> Option<&mut StringCursor> {
         Some(&mut self.input)
     }
@@ -98,11 +214,35 @@ impl PromptInteraction<String> for Password {
         false
     }
 
-    fn on(&mut self, event: &Event) -> State<String> {
+    fn transcript(&self) -> Option<(String, String)> {
+        // Don't reveal the password length either.
+        Some((self.prompt.clone(), self.mask.to_string().repeat(8)))
+    }
+
+    fn on(&mut self, event: &Event) -> State<SecretString> {
         let Event::Key(key) = event;
 
+        // Ctrl-R
+        if *key == Key::Char('\u{12}') && self.allow_reveal {
+            self.revealed = !self.revealed;
+            return State::Active;
+        }
+
+        if let Some(entered) = &self.entered {
+            if *key != Key::Enter {
+                return State::Active;
+            }
+            if entered.same_content(&self.input) {
+                return State::Submit(SecretString::new(self.input.to_zeroizing()));
+            }
+            // Start over.
+            self.entered = None;
+            self.input.clear();
+            return State::Error("Passwords do not match".to_string());
+        }
+
         if let Some(validator) = &self.validate_interactively {
-            if let Err(err) = validator(&self.input.to_string()) {
+            if let Err(err) = validator(&self.input.to_zeroizing()) {
                 return State::Error(err);
             }
         }
@@ -113,31 +253,91 @@ impl PromptInteraction<String> for Password {
             }
 
             if let Some(validator) = &self.validate_on_enter {
-                if let Err(err) = validator(&self.input.to_string()) {
+                if let Err(err) = validator(&self.input.to_zeroizing()) {
                     return State::Error(err);
                 }
             }
 
-            if *key == Key::Enter {
-                return State::Submit(self.input.to_string());
+            if self.confirm.is_some() {
+                self.entered = Some(std::mem::take(&mut self.input));
+                return State::Active;
             }
+
+            return State::Submit(SecretString::new(self.input.to_zeroizing()));
         }
 
         State::Active
     }
 
-    fn render(&mut self, state: &State<String>) -> String {
-        let mut masked = self.input.clone();
-        for chr in masked.iter_mut() {
-            *chr = self.mask;
-        }
+    fn render(&mut self, state: &State<SecretString>) -> String {
+        let active = matches!(state, State::Active | State::Error(_));
 
         let theme = THEME.read().unwrap();
 
-        let line1 = theme.format_header(&state.into(), &self.prompt);
-        let line2 = theme.format_input(&state.into(), &masked);
-        let line3 = theme.format_footer(&state.into());
+        let prompt = match (&self.entered, &self.confirm) {
+            (Some(_), Some(confirm)) if active => confirm,
+            _ => &self.prompt,
+        };
+        let line1 = theme.format_header(&state.into(), prompt);
+        // Render a masked copy not to copy the password itself.
+        let line2 = if self.revealed && active {
+            theme.format_input(&state.into(), &self.input)
+        } else {
+            theme.format_input(&state.into(), &self.input.masked(self.mask))
+        };
+
+        let strength = match &self.strength {
+            Some(estimator) if active && self.entered.is_none() && !self.input.is_empty() => {
+                let strength = estimator(&self.input.to_zeroizing());
+                theme.format_password_strength(&state.into(), strength)
+            }
+            _ => String::new(),
+        };
+
+        let footer_message = match (self.allow_reveal, self.revealed) {
+            (true, false) => "[Ctrl-R](Reveal)",
+            (true, true) => "[Ctrl-R](Hide)",
+            _ => "",
+        };
+        let line3 = theme.format_footer_with_message(&state.into(), footer_message);
+
+        line1 + &line2 + &strength + &line3
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enter(password: &mut Password, text: &str) -> State<SecretString> {
+        password.input.extend(text);
+        password.on(&Event::Key(Key::Enter))
+    }
+
+    #[test]
+    fn confirmation() {
+        let mut password = Password::new("Password").confirm("Repeat");
+        assert!(matches!(enter(&mut password, "secret"), State::Active));
+        match enter(&mut password, "secrets") {
+            State::Error(err) => assert_eq!(err, "Passwords do not match"),
+            _ => panic!("expected a mismatch"),
+        }
+        assert!(matches!(enter(&mut password, "secret"), State::Active));
+        match enter(&mut password, "secret") {
+            State::Submit(value) => assert_eq!(value.expose_secret(), "secret"),
+            _ => panic!("expected a password"),
+        }
+    }
 
-        line1 + &line2 + &line3
+    #[test]
+    fn strength() {
+        assert_eq!(Strength::estimate("1234"), Strength::VeryWeak);
+        assert_eq!(Strength::estimate("secret"), Strength::Weak);
+        assert_eq!(Strength::estimate("Passw0rd!"), Strength::Fair);
+        assert_eq!(Strength::estimate("Tr0ub4dor&3xyz"), Strength::Strong);
+        assert_eq!(
+            Strength::estimate("correct horse battery staple"),
+            Strength::VeryStrong
+        );
     }
 }
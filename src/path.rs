@@ -0,0 +1,380 @@
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{is_separator, PathBuf};
+
+use crate::{
+    autocomplete::{Autocomplete, AutocompleteResult},
+    input::Input,
+};
+
+/// Returns the home directory of the current user.
+fn home_dir() -> Option<PathBuf> {
+    let var = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+    env::var_os(var).map(PathBuf::from)
+}
+
+/// Expands the leading `~` into the home directory, e.g. `~/projects`.
+pub(crate) fn expand_tilde(path: &str) -> PathBuf {
+    let mut chars = path.chars();
+    if chars.next() == Some('~') {
+        let rest = chars.as_str();
+        if rest.is_empty() || rest.starts_with(is_separator) {
+            if let Some(home) = home_dir() {
+                return match rest.trim_start_matches(is_separator) {
+                    "" => home,
+                    rest => home.join(rest),
+                };
+            }
+        }
+    }
+    PathBuf::from(path)
+}
+
+/// Returns the longest common prefix of the suggested values.
+fn common_prefix(suggestions: &[String]) -> Option<String> {
+    let (first, rest) = suggestions.split_first()?;
+    let mut prefix = first.as_str();
+    for item in rest {
+        let len = prefix
+            .char_indices()
+            .zip(item.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(prefix.len().min(item.len()), |((ix, _), _)| ix);
+        prefix = &prefix[..len];
+    }
+    Some(prefix.to_string())
+}
+
+/// The kind of a filesystem entry.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PathKind {
+    /// A regular file.
+    File,
+    /// A directory.
+    Directory,
+}
+
+/// Completes file and directory paths for the [`Input`] prompt.
+///
+/// * A leading `~` is expanded into the home directory.
+/// * Relative paths are resolved against the base directory
+///   (the current directory by default).
+/// * Directories are suggested with a trailing slash.
+/// * Hidden entries are only suggested if the typed name starts with a dot.
+///
+/// ```
+/// use cliclack::{Input, PathCompleter};
+///
+/// # fn test() -> std::io::Result<()> {
+/// let config: String = Input::new("Config file")
+///     .autocompleter(PathCompleter::new().extensions(["toml"]))
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct PathCompleter {
+    base_dir: Option<PathBuf>,
+    extensions: Vec<String>,
+    kind: Option<PathKind>,
+}
+
+impl PathCompleter {
+    /// Creates a completer of paths relative to the current directory.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the directory the relative paths are resolved against.
+    pub fn base_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.base_dir = Some(dir.into());
+        self
+    }
+
+    /// Suggests only the files with given extensions (and directories),
+    /// e.g. `["toml", "json"]`.
+    pub fn extensions<I, S>(mut self, extensions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Display,
+    {
+        self.extensions = extensions
+            .into_iter()
+            .map(|ext| ext.to_string().trim_start_matches('.').to_string())
+            .collect();
+        self
+    }
+
+    /// Suggests only the entries of a given kind.
+    ///
+    /// Directories are always suggested to navigate into them,
+    /// so `PathKind::File` affects only the validation of [`PathInput`].
+    pub fn kind(mut self, kind: PathKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Resolves the typed path: expands `~` and joins a relative path to the base directory.
+    pub fn resolve(&self, path: &str) -> PathBuf {
+        let path = expand_tilde(path);
+        match &self.base_dir {
+            Some(base_dir) if path.is_relative() => base_dir.join(path),
+            _ => path,
+        }
+    }
+
+    fn has_extension(&self, name: &str) -> bool {
+        self.extensions.is_empty()
+            || name
+                .rsplit_once('.')
+                .is_some_and(|(_, ext)| self.extensions.iter().any(|e| e == ext))
+    }
+}
+
+impl Autocomplete for PathCompleter {
+    fn get_suggestions(&mut self, input: &str) -> AutocompleteResult {
+        if input.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // The typed directory (as is, to keep `~` and relative paths) and the name prefix.
+        let (dir, prefix) = match input.rfind(is_separator) {
+            Some(ix) => input.split_at(ix + 1),
+            None => ("", input),
+        };
+        let entries = match fs::read_dir(self.resolve(if dir.is_empty() { "." } else { dir })) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err.to_string()),
+        };
+
+        let mut suggestions = vec![];
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.')) {
+                continue;
+            }
+
+            if entry.path().is_dir() {
+                suggestions.push(format!("{dir}{name}/"));
+            } else if self.kind != Some(PathKind::Directory) && self.has_extension(&name) {
+                suggestions.push(format!("{dir}{name}"));
+            }
+        }
+        suggestions.sort();
+
+        Ok(suggestions)
+    }
+
+    fn get_completion(&mut self, input: &str, highlighted: Option<String>) -> Option<String> {
+        // Like a shell: complete the common part of all matching entries.
+        highlighted.or_else(|| {
+            let suggestions = self.get_suggestions(input).ok()?;
+            common_prefix(&suggestions).filter(|prefix| prefix.len() > input.len())
+        })
+    }
+}
+
+/// A prompt that accepts a filesystem path with completion.
+///
+/// Press `Tab` or arrow keys to cycle through the matching files and
+/// directories (see [`PathCompleter`]). The path is checked on submit
+/// against the expected kind, extensions, and existence.
+///
+/// # Example
+///
+/// ```
+/// use cliclack::PathInput;
+///
+/// # fn test() -> std::io::Result<()> {
+/// let path = PathInput::new("Where should we create your project?")
+///     .placeholder("./my-app")
+///     .directory()
+///     .must_not_exist()
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
+pub struct PathInput {
+    input: Input,
+    completer: PathCompleter,
+    exists: Option<bool>,
+}
+
+impl PathInput {
+    /// Creates a new path prompt.
+    pub fn new(prompt: impl Display) -> Self {
+        Self {
+            input: Input::new(prompt),
+            completer: PathCompleter::new(),
+            exists: None,
+        }
+    }
+
+    /// Sets the placeholder (hint) text for the input.
+    pub fn placeholder(mut self, placeholder: &str) -> Self {
+        self.input = self.input.placeholder(placeholder);
+        self
+    }
+
+    /// Sets the default value for the input (see [`Input::default_input`]).
+    pub fn default_input(mut self, value: &str) -> Self {
+        self.input = self.input.default_input(value);
+        self
+    }
+
+    /// Sets the directory the relative paths are resolved against.
+    /// Default: the current directory.
+    pub fn base_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.completer = self.completer.base_dir(dir);
+        self
+    }
+
+    /// Accepts only the files with given extensions, e.g. `["toml", "json"]`.
+    pub fn extensions<I, S>(mut self, extensions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Display,
+    {
+        self.completer = self.completer.extensions(extensions);
+        self
+    }
+
+    /// Expects a file (if the path exists).
+    pub fn file(mut self) -> Self {
+        self.completer = self.completer.kind(PathKind::File);
+        self
+    }
+
+    /// Expects a directory (if the path exists).
+    pub fn directory(mut self) -> Self {
+        self.completer = self.completer.kind(PathKind::Directory);
+        self
+    }
+
+    /// Requires the path to exist.
+    pub fn must_exist(mut self) -> Self {
+        self.exists = Some(true);
+        self
+    }
+
+    /// Requires the path not to exist, e.g. to create a new project.
+    pub fn must_not_exist(mut self) -> Self {
+        self.exists = Some(false);
+        self
+    }
+
+    /// Checks the typed path returning the resolved one.
+    fn check(&self, value: &str) -> Result<PathBuf, String> {
+        let path = self.completer.resolve(value);
+
+        match self.exists {
+            Some(true) if !path.exists() => return Err("The path does not exist".into()),
+            Some(false) if path.exists() => return Err("The path already exists".into()),
+            _ => {}
+        }
+
+        match self.completer.kind {
+            Some(PathKind::File) if path.is_dir() => return Err("Expected a file".into()),
+            Some(PathKind::Directory) if path.exists() && !path.is_dir() => {
+                return Err("Expected a directory".into())
+            }
+            _ => {}
+        }
+
+        let is_file = !path.is_dir() && self.completer.kind != Some(PathKind::Directory);
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if is_file && !self.completer.has_extension(&name) {
+            return Err(format!(
+                "Expected a file with the extension: {}",
+                self.completer.extensions.join(", ")
+            ));
+        }
+
+        Ok(path)
+    }
+
+    /// Starts the prompt interaction returning the resolved path:
+    /// with `~` expanded and joined to the base directory.
+    pub fn interact(&mut self) -> io::Result<PathBuf> {
+        let mut input = std::mem::take(&mut self.input).autocompleter(self.completer.clone());
+        let result = input.interact_parsed(Box::new(|value: &str| self.check(value)), false);
+        self.input = input;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tilde() {
+        // Without a home directory, `~` is kept as is.
+        match home_dir() {
+            Some(home) => {
+                assert_eq!(expand_tilde("~"), home);
+                assert_eq!(expand_tilde("~/projects"), home.join("projects"));
+            }
+            None => assert_eq!(expand_tilde("~/projects"), PathBuf::from("~/projects")),
+        }
+        assert_eq!(expand_tilde("~user"), PathBuf::from("~user"));
+        assert_eq!(expand_tilde("a/~"), PathBuf::from("a/~"));
+    }
+
+    fn values(completer: &mut PathCompleter, input: &str) -> Vec<String> {
+        completer.get_suggestions(input).unwrap()
+    }
+
+    #[test]
+    fn completion() {
+        let base = env::temp_dir().join(format!("cliclack-path-{}", std::process::id()));
+        fs::create_dir_all(base.join("src")).unwrap();
+        fs::create_dir_all(base.join(".git")).unwrap();
+        for file in ["Cargo.toml", "Cargo.lock", "src/main.rs", "src/lib.rs"] {
+            fs::write(base.join(file), "").unwrap();
+        }
+
+        let mut completer = PathCompleter::new().base_dir(&base);
+        let suggestions = values(&mut completer, "Ca");
+        let completion = completer.get_completion("C", None);
+        let hidden = values(&mut completer, "s");
+        let nested = values(&mut completer, "src/");
+        let dot = values(&mut completer, ".");
+        let mut toml = PathCompleter::new().base_dir(&base).extensions([".toml"]);
+        let filtered = values(&mut toml, "C");
+        fs::remove_dir_all(&base).unwrap();
+
+        assert_eq!(suggestions, vec!["Cargo.lock", "Cargo.toml"]);
+        assert_eq!(completion.as_deref(), Some("Cargo."));
+        assert_eq!(hidden, vec!["src/"]);
+        assert_eq!(nested, vec!["src/lib.rs", "src/main.rs"]);
+        assert_eq!(dot, vec![".git/"]);
+        assert_eq!(filtered, vec!["Cargo.toml"]);
+    }
+
+    #[test]
+    fn check() {
+        let base = env::temp_dir();
+        let prompt = PathInput::new("Path")
+            .base_dir(&base)
+            .directory()
+            .must_exist();
+        assert_eq!(prompt.check("."), Ok(base.join(".")));
+        assert_eq!(
+            prompt.check("cliclack-surely-missing"),
+            Err("The path does not exist".to_string())
+        );
+
+        let prompt = PathInput::new("Path").extensions(["toml"]);
+        assert_eq!(
+            prompt.check("config.json"),
+            Err("Expected a file with the extension: toml".to_string())
+        );
+        assert!(prompt.check("config.toml").is_ok());
+    }
+}
@@ -6,7 +6,12 @@ use std::{
 
 use indicatif::ProgressStyle;
 
-use crate::{theme::THEME, ThemeState};
+use crate::{
+    output,
+    theme::THEME,
+    transcript::{self, Entry},
+    ThemeState,
+};
 
 #[derive(Default)]
 pub(crate) struct ProgressBarState {
@@ -31,7 +36,7 @@ impl ProgressBar {
     /// Creates a new progress bar with a given length.
     pub fn new(len: u64) -> Self {
         let this = Self {
-            bar: indicatif::ProgressBar::new(len),
+            bar: indicatif::ProgressBar::with_draw_target(Some(len), output::draw_target()),
             options: Default::default(),
         };
 
@@ -165,7 +170,7 @@ impl ProgressBar {
 
         // Ignore a cleared progress bar.
         if !message.to_string().is_empty() {
-            self.bar.println(render.clone());
+            output::println(&self.bar, &render);
         }
 
         render.lines().count()
@@ -220,6 +225,8 @@ impl ProgressBar {
 
         self.options_write().stopped = true;
 
+        record_outcome(&message.to_string(), state);
+
         if !self.options().grouped {
             // Workaround: `bar.println` must be before `bar.finish_and_clear` to avoid "jumping"
             // of the printed line while resizing the terminal.
@@ -232,3 +239,22 @@ impl ProgressBar {
         }
     }
 }
+
+/// Records the final state of a progress bar into the session transcript.
+pub(crate) fn record_outcome(message: &str, state: &ThemeState) {
+    // Ignore a cleared progress bar.
+    if message.is_empty() {
+        return;
+    }
+
+    let outcome = match state {
+        ThemeState::Active | ThemeState::Submit => "done",
+        ThemeState::Cancel => "cancelled",
+        ThemeState::Error(_) => "error",
+    };
+
+    transcript::record(Entry::Progress {
+        outcome,
+        message: message.to_string(),
+    });
+}
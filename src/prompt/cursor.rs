@@ -1,15 +1,35 @@
 use std::fmt::{Display, Formatter, Result};
+use std::ops::Range;
 
-use zeroize::ZeroizeOnDrop;
+use console::Style;
+use icu_segmenter::GraphemeClusterSegmenter;
+use unicode_width::UnicodeWidthStr;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+use crate::mask::InputMask;
 
 /// A cursor for editing multiline strings.
 ///
 /// Supports moving the cursor (left, right, up, down), backspace, delete, etc.
+/// The cursor moves and deletes by grapheme clusters (user-perceived characters
+/// like emoji with modifiers or letters with combining accents), and the soft
+/// wrapping accounts for the display width (e.g. of CJK wide characters).
 #[doc(hidden)]
 #[derive(Default, ZeroizeOnDrop, Clone)]
 pub struct StringCursor {
     value: Vec<char>,
     cursor: usize,
+    #[zeroize(skip)]
+    mask: Option<InputMask>,
+    /// The width the lines are wrapped at on the screen (soft wrapping).
+    #[zeroize(skip)]
+    wrap_width: Option<usize>,
+    /// The styled ranges of characters (see [`StringCursor::set_highlights`]).
+    #[zeroize(skip)]
+    highlights: Vec<(Range<usize>, Style)>,
+    /// The maximum number of characters (see [`StringCursor::set_max_len`]).
+    #[zeroize(skip)]
+    max_len: Option<usize>,
 }
 
 /// Returns the indices of the first character of each word in the given string,
@@ -41,82 +61,374 @@ fn line_jump_indices(value: &[char]) -> Vec<usize> {
     })
 }
 
+/// A grapheme cluster: a user-perceived character.
+struct Cluster {
+    /// The range of the characters.
+    chars: Range<usize>,
+    /// The display width in columns.
+    width: usize,
+    /// Whether it's a line break.
+    newline: bool,
+}
+
+/// Returns the visual lines (rows) of the text split into grapheme clusters:
+/// the lines wrapped at a given display width. The ranges exclude the line breaks.
+///
+/// A line filling the width exactly is followed by an empty row, so that
+/// the cursor at the end of the line has a place to be shown.
+fn visual_lines(clusters: &[Cluster], len: usize, width: usize) -> Vec<Range<usize>> {
+    let width = width.max(1);
+    let mut rows = vec![];
+    let mut start = 0;
+    let mut row_width: usize = 0;
+
+    for cluster in clusters {
+        if cluster.newline {
+            rows.push(start..cluster.chars.start);
+            start = cluster.chars.end;
+            row_width = 0;
+            continue;
+        }
+
+        // A wide character doesn't fit at the end of the row.
+        if row_width > 0 && row_width.saturating_add(cluster.width) > width {
+            rows.push(start..cluster.chars.start);
+            start = cluster.chars.start;
+            row_width = 0;
+        }
+        row_width += cluster.width;
+        if row_width >= width {
+            rows.push(start..cluster.chars.end);
+            start = cluster.chars.end;
+            row_width = 0;
+        }
+    }
+    rows.push(start..len);
+
+    rows
+}
+
 impl StringCursor {
+    /// Sets the mask which the content is conformed to after each change.
+    pub fn set_mask(&mut self, mask: InputMask) {
+        self.mask = Some(mask);
+        self.conform();
+    }
+
+    /// Returns the mask of the content if set.
+    pub fn mask(&self) -> Option<&InputMask> {
+        self.mask.as_ref()
+    }
+
+    /// Returns the part of the mask template which is not filled yet.
+    pub fn mask_remaining(&self) -> Option<String> {
+        Some(self.mask.as_ref()?.remaining(&self.value))
+    }
+
+    /// Returns `true` if all required slots of the mask are filled (or there
+    /// is no mask).
+    pub fn is_mask_complete(&self) -> bool {
+        self.mask
+            .as_ref()
+            .is_none_or(|mask| mask.is_complete(&self.value))
+    }
+
+    /// Applies the mask (if any) to the content.
+    fn conform(&mut self) {
+        if let Some(mask) = &self.mask {
+            let conformed = mask.conform(&self.value, self.cursor);
+            self.value = conformed.value;
+            self.cursor = conformed.cursor;
+        }
+    }
+
+    /// Returns `true` if the cursor can stay at a given position: masked
+    /// content allows the cursor only after the entered characters, skipping
+    /// the literals of the template.
+    fn is_stop(&self, position: usize) -> bool {
+        match &self.mask {
+            Some(mask) if position > 0 && position < self.value.len() => {
+                !mask.conform(&self.value, 0).literals[position - 1]
+            }
+            _ => true,
+        }
+    }
+
+    /// Sets the styles of the character ranges used to highlight the content,
+    /// e.g. to highlight syntax or an invalid part.
+    pub fn set_highlights(&mut self, highlights: Vec<(Range<usize>, Style)>) {
+        self.highlights = highlights;
+    }
+
+    /// Splits the characters in a given range into the runs of the same
+    /// highlighting: the text and its style if it's highlighted.
+    pub fn highlighted(&self, range: Range<usize>) -> Vec<(String, Option<&Style>)> {
+        let mut runs: Vec<(String, Option<&Style>)> = vec![];
+        let end = range.end.min(self.value.len());
+
+        for ix in range.start.min(end)..end {
+            let style = self
+                .highlights
+                .iter()
+                .find(|(range, _)| range.contains(&ix))
+                .map(|(_, style)| style);
+            match runs.last_mut() {
+                Some((text, last)) if *last == style => text.push(self.value[ix]),
+                _ => runs.push((self.value[ix].to_string(), style)),
+            }
+        }
+
+        runs
+    }
+
+    /// Limits the number of characters: the insertion beyond the limit
+    /// is ignored. `None` removes the limit.
+    pub fn set_max_len(&mut self, max_len: Option<usize>) {
+        self.max_len = max_len;
+    }
+
+    /// Returns `true` if the content has reached the maximum length.
+    pub fn is_full(&self) -> bool {
+        self.max_len.is_some_and(|max| self.value.len() >= max)
+    }
+
+    /// Returns the number of characters.
+    pub fn len(&self) -> usize {
+        self.value.len()
+    }
+
     /// Returns `true` if the cursor contains no characters.
     pub fn is_empty(&self) -> bool {
         self.value.is_empty()
     }
 
+    /// Splits the content into grapheme clusters.
+    fn clusters(&self) -> Vec<Cluster> {
+        let text = self.to_zeroizing();
+        // The boundaries are increasing, so the characters are scanned once.
+        let mut chars = (text.char_indices().map(|(ix, _)| ix))
+            .chain([text.len()])
+            .enumerate();
+        let mut char_of = |byte: usize| {
+            chars
+                .find(|&(_, ix)| ix == byte)
+                .map_or(self.value.len(), |(char_ix, _)| char_ix)
+        };
+
+        let segmenter = GraphemeClusterSegmenter::new();
+        let mut clusters = vec![];
+        let mut last = (0, char_of(0));
+        for boundary in segmenter.segment_str(&text).filter(|&b| b > 0) {
+            let end = (boundary, char_of(boundary));
+            let cluster = &text[last.0..boundary];
+            clusters.push(Cluster {
+                chars: last.1..end.1,
+                width: cluster.width(),
+                newline: cluster.contains('\n'),
+            });
+            last = end;
+        }
+
+        clusters
+    }
+
+    /// Returns the character indices of the grapheme cluster boundaries,
+    /// including the start and the end of the content.
+    fn boundaries(&self) -> Vec<usize> {
+        let mut boundaries = vec![0];
+        boundaries.extend(self.clusters().iter().map(|cluster| cluster.chars.end));
+        boundaries
+    }
+
+    /// Returns the index of the end of the grapheme cluster at the cursor position.
+    pub fn current_end(&self) -> usize {
+        let boundaries = self.boundaries();
+        boundaries
+            .get(boundaries.partition_point(|&b| b <= self.cursor))
+            .copied()
+            .unwrap_or(self.value.len())
+    }
+
     /// Returns a character at the current cursor position.
     pub fn current(&self) -> Option<char> {
         self.value.get(self.cursor).copied()
     }
 
     /// Inserts a character at the current cursor position.
+    ///
+    /// Does nothing if the content has reached the maximum length.
     pub fn insert(&mut self, chr: char) {
+        if self.is_full() {
+            return;
+        }
         self.value.insert(self.cursor, chr);
         self.cursor += 1;
+        self.conform();
     }
 
-    /// Moves the cursor one position left.
+    /// Moves the cursor one position (grapheme cluster) left.
     pub fn move_left(&mut self) {
-        if self.cursor > 0 {
-            self.cursor -= 1;
+        let boundaries = self.boundaries();
+        let ix = boundaries.partition_point(|&b| b < self.cursor);
+        for &boundary in boundaries[..ix].iter().rev() {
+            self.cursor = boundary;
+            if self.is_stop(boundary) {
+                break;
+            }
         }
     }
 
-    /// Moves the cursor one position right.
+    /// Moves the cursor one position (grapheme cluster) right.
     pub fn move_right(&mut self) {
-        if self.cursor < self.value.len() {
-            self.cursor += 1;
+        let boundaries = self.boundaries();
+        let ix = boundaries.partition_point(|&b| b <= self.cursor);
+        for &boundary in &boundaries[ix..] {
+            self.cursor = boundary;
+            if self.is_stop(boundary) {
+                break;
+            }
         }
     }
 
-    /// Moves the cursor one position up considering multiline representation.
-    pub fn move_up(&mut self) {
-        let jumps = line_jump_indices(&self.value);
-        self.cursor = match jumps.binary_search(&self.cursor) {
-            Ok(ix) if ix + 1 < jumps.len() => {
-                // Happened to be at the start of a line.
-                let target_line = ix.saturating_sub(1);
-                jumps[target_line]
+    /// Sets the width the lines are wrapped at on the screen, so that moving
+    /// up and down follows the visual lines. `None` disables soft wrapping.
+    pub fn set_wrap_width(&mut self, width: Option<usize>) {
+        self.wrap_width = width;
+    }
+
+    /// Returns the visual lines (rows) of the content considering soft wrapping
+    /// (see [`StringCursor::set_wrap_width`]). The ranges exclude the line breaks.
+    pub fn visual_lines(&self) -> Vec<Range<usize>> {
+        let width = self.wrap_width.unwrap_or(usize::MAX);
+        visual_lines(&self.clusters(), self.value.len(), width)
+    }
+
+    /// Returns the index of the visual line (row) with the cursor.
+    pub fn visual_row(&self) -> usize {
+        Self::row_of(&self.visual_lines(), self.cursor)
+    }
+
+    /// Returns the index of the character at the cursor position.
+    pub fn position(&self) -> usize {
+        self.cursor
+    }
+
+    /// Returns the characters in a given range as a string.
+    pub fn substring(&self, range: Range<usize>) -> String {
+        String::from_iter(&self.value[range])
+    }
+
+    /// Returns a cursor with the characters in a given range (e.g. a row on
+    /// the screen) and their highlights. The cursor position is clamped
+    /// to the range.
+    pub fn slice(&self, range: Range<usize>) -> StringCursor {
+        let shift = |ix: usize| ix.clamp(range.start, range.end) - range.start;
+        let highlights = self
+            .highlights
+            .iter()
+            .map(|(chars, style)| (shift(chars.start)..shift(chars.end), style.clone()))
+            .filter(|(chars, _)| !chars.is_empty())
+            .collect();
+
+        StringCursor {
+            value: self.value[range.clone()].to_vec(),
+            cursor: shift(self.cursor),
+            mask: None,
+            wrap_width: None,
+            highlights,
+            max_len: None,
+        }
+    }
+
+    /// Returns the index of the row containing a given position.
+    fn row_of(rows: &[Range<usize>], position: usize) -> usize {
+        rows.partition_point(|row| row.start <= position)
+            .saturating_sub(1)
+    }
+
+    /// Returns the range of characters shown on a single row of a given
+    /// display width, starting from `start` (the previous window start)
+    /// and scrolled just enough to show the cursor.
+    ///
+    /// A column is reserved for the cursor at the end of the content,
+    /// and for the markers of the hidden text on either side.
+    pub fn horizontal_window(&self, start: usize, width: usize) -> Range<usize> {
+        let clusters = self.clusters();
+        // The width of each cell, with the cursor cell after the content.
+        let cells: Vec<usize> = (clusters.iter().map(|c| c.width)).chain([1]).collect();
+        let cell_of = |position: usize| clusters.partition_point(|c| c.chars.end <= position);
+        let cursor = cell_of(self.cursor);
+        let hidden_after = |end: usize| usize::from(end < clusters.len());
+
+        // Scroll left to the cursor, or right until it fits.
+        let mut first = cell_of(start).min(cursor);
+        let used = |first: usize, end: usize| {
+            cells[first..end].iter().sum::<usize>() + usize::from(first > 0) + hidden_after(end)
+        };
+        while first < cursor && used(first, cursor + 1) > width {
+            first += 1;
+        }
+
+        // Fill the rest of the row.
+        let mut end = cursor + 1;
+        while end < clusters.len() && used(first, end + 1) <= width {
+            end += 1;
+        }
+
+        let char_start = clusters
+            .get(first)
+            .map_or(self.value.len(), |c| c.chars.start);
+        let char_end = match end.min(clusters.len()) {
+            0 => 0,
+            end => clusters[end - 1].chars.end,
+        };
+        char_start..char_end.max(char_start)
+    }
+
+    /// Moves the cursor to the row above (`up`) or below keeping the column
+    /// on the screen, or to the end of the row if it's shorter.
+    fn move_vertically(&mut self, up: bool) {
+        let clusters = self.clusters();
+        let width = self.wrap_width.unwrap_or(usize::MAX);
+        let rows = visual_lines(&clusters, self.value.len(), width);
+        let row = Self::row_of(&rows, self.cursor);
+
+        let target = match up {
+            true if row == 0 => {
+                self.cursor = 0;
+                return;
             }
-            Ok(ix) | Err(ix) => {
-                let ix = ix.saturating_sub(1); // current line
-                let target_line = ix.saturating_sub(1);
-                let offset = std::cmp::min(
-                    self.cursor - jumps[ix],
-                    (jumps[ix] - jumps[target_line]).saturating_sub(1),
-                );
-                jumps[target_line] + offset
+            true => &rows[row - 1],
+            false if row + 1 == rows.len() => return,
+            false => &rows[row + 1],
+        };
+
+        let in_row = |range: Range<usize>| {
+            clusters.iter().filter(move |c| {
+                !c.newline && c.chars.start >= range.start && c.chars.end <= range.end
+            })
+        };
+        let column: usize = in_row(rows[row].start..self.cursor).map(|c| c.width).sum();
+
+        let mut width = 0;
+        self.cursor = target.end;
+        for cluster in in_row(target.clone()) {
+            if width + cluster.width > column {
+                self.cursor = cluster.chars.start;
+                break;
             }
+            width += cluster.width;
         }
     }
 
+    /// Moves the cursor one position up considering multiline representation.
+    pub fn move_up(&mut self) {
+        self.move_vertically(true);
+    }
+
     /// Moves the cursor one position down considering multiline representation.
     pub fn move_down(&mut self) {
-        let jumps = line_jump_indices(&self.value);
-        self.cursor = match jumps.binary_search(&self.cursor) {
-            Ok(ix) if ix + 1 < jumps.len() => {
-                // Happened to be at the start of a line.
-                let target_line = std::cmp::min(ix + 1, jumps.len().saturating_sub(2));
-                jumps[target_line]
-            }
-            Ok(ix) => {
-                // Happened to be at the end of string.
-                jumps[ix].saturating_sub(1)
-            }
-            Err(ix) => {
-                let ix = ix.saturating_sub(1); // current line
-                let target_line = std::cmp::min(ix + 1, jumps.len().saturating_sub(2));
-                let target_next = std::cmp::min(target_line + 1, jumps.len().saturating_sub(1));
-                let offset = std::cmp::min(
-                    self.cursor - jumps[ix],
-                    (jumps[target_next] - jumps[target_line]).saturating_sub(1),
-                );
-                jumps[target_line] + offset
-            }
-        }
+        self.move_vertically(false);
     }
 
     /// Moves the cursor left by a word.
@@ -160,8 +472,11 @@ impl StringCursor {
         }
 
         if self.cursor > 0 {
-            self.value.remove(self.cursor - 1);
-            self.cursor -= 1;
+            let boundaries = self.boundaries();
+            let start = boundaries[boundaries.partition_point(|&b| b < self.cursor) - 1];
+            self.value.drain(start..self.cursor);
+            self.cursor = start;
+            self.conform();
         }
     }
 
@@ -172,7 +487,9 @@ impl StringCursor {
         }
 
         if self.cursor < self.value.len() {
-            self.value.remove(self.cursor);
+            let end = self.current_end();
+            self.value.drain(self.cursor..end);
+            self.conform();
         }
     }
 
@@ -185,18 +502,47 @@ impl StringCursor {
             let end = self.cursor;
             self.value.drain(start..end);
             self.cursor = start;
+            self.conform();
         }
     }
 
-    /// Clears the cursor, removing all characters.
+    /// Clears the cursor, removing all characters (zeroing the memory).
     pub fn clear(&mut self) {
         self.cursor = 0;
-        self.value.clear()
+        self.value.zeroize();
+    }
+
+    /// Returns the content as a string which is zeroed on drop, without
+    /// leaving intermediate copies in memory.
+    pub fn to_zeroizing(&self) -> Zeroizing<String> {
+        let mut text = String::with_capacity(self.value.iter().map(|c| c.len_utf8()).sum());
+        text.extend(&self.value);
+        Zeroizing::new(text)
+    }
+
+    /// Returns a copy with each character replaced by a mask character,
+    /// keeping the cursor position (e.g. to render a password).
+    pub fn masked(&self, mask: char) -> Self {
+        let mut masked = Self::default();
+        masked.value = vec![mask; self.value.len()];
+        masked.cursor = self.cursor;
+        masked
+    }
+
+    /// Returns `true` if the content is the same as of another cursor.
+    pub fn same_content(&self, other: &Self) -> bool {
+        self.value == other.value
     }
 
     /// Extends the cursor with the contents of a given string.
+    ///
+    /// The characters beyond the maximum length are dropped.
     pub fn extend(&mut self, string: &str) {
-        self.value.extend(string.chars());
+        let room = self
+            .max_len
+            .map_or(usize::MAX, |max| max.saturating_sub(self.value.len()));
+        self.value.extend(string.chars().take(room));
+        self.conform();
     }
 
     /// Splits the cursor into three parts: left, cursor, and right.
@@ -205,15 +551,16 @@ impl StringCursor {
         let mut cursor = String::from(' ');
         let mut right = String::new();
 
-        match self.current() {
-            Some('\n') => right.push('\n'),
-            Some(chr) => cursor = chr.to_string(),
-            None => {}
+        let end = match self.current() {
+            Some('\n') => self.cursor,
+            Some(_) => {
+                let end = self.current_end();
+                cursor = String::from_iter(&self.value[self.cursor..end]);
+                end
+            }
+            None => self.cursor,
         };
-
-        if !self.value.is_empty() && self.cursor < self.value.len() - 1 {
-            right.push_str(&String::from_iter(&self.value[self.cursor + 1..]));
-        }
+        right.push_str(&String::from_iter(&self.value[end..]));
 
         (left, cursor, right)
     }
@@ -251,6 +598,10 @@ mod test {
         let mut cursor = StringCursor {
             value: "hello\nworld".chars().collect(),
             cursor: 0,
+            mask: None,
+            wrap_width: None,
+            highlights: vec![],
+            max_len: None,
         };
         assert_cursor!(cursor, 'h');
         assert_content!(cursor, "hello\nworld");
@@ -286,4 +637,133 @@ mod test {
         cursor.move_home();
         assert_cursor!(cursor, 'w');
     }
+
+    #[test]
+    fn test_wrapped_cursor() {
+        let mut cursor = StringCursor::default();
+        cursor.extend("abcdefghij\nxy\nabcd");
+        cursor.set_wrap_width(Some(4));
+        assert_eq!(
+            cursor.visual_lines(),
+            vec![0..4, 4..8, 8..10, 11..13, 14..18, 18..18]
+        );
+
+        cursor.move_right();
+        cursor.move_down();
+        assert_cursor!(cursor, 'f');
+        cursor.move_down();
+        cursor.move_down();
+        assert_cursor!(cursor, 'y');
+        assert_eq!(cursor.visual_row(), 3);
+        cursor.move_up();
+        cursor.move_up();
+        assert_cursor!(cursor, 'f');
+        for _ in 0..3 {
+            cursor.move_down();
+        }
+        cursor.move_end();
+        assert_eq!(cursor.visual_row(), 5);
+        cursor.move_up();
+        assert_cursor!(cursor, 'a');
+    }
+
+    #[test]
+    fn test_grapheme_cursor() {
+        let mut cursor = StringCursor::default();
+        cursor.extend("ae\u{301}👍🏽x");
+        cursor.move_end();
+        cursor.move_left();
+        cursor.move_left();
+        assert_eq!(cursor.position(), 3);
+        assert_eq!(cursor.split().1, "👍🏽");
+        cursor.delete_left();
+        assert_content!(cursor, "a👍🏽x");
+        cursor.delete_right();
+        assert_content!(cursor, "ax");
+
+        let mut cursor = StringCursor::default();
+        cursor.extend("a漢字\n字");
+        cursor.set_wrap_width(Some(4));
+        assert_eq!(cursor.visual_lines(), vec![0..2, 2..3, 4..5]);
+        cursor.move_down();
+        cursor.move_down();
+        cursor.move_end();
+        cursor.move_up();
+        assert_eq!(cursor.position(), 3);
+        cursor.move_up();
+        assert_eq!(cursor.position(), 1);
+    }
+
+    #[test]
+    fn test_slice() {
+        let mut cursor = StringCursor::default();
+        cursor.extend("let x = 1;\nx");
+        cursor.set_highlights(vec![(0..3, Style::new().bold()), (8..9, Style::new())]);
+        cursor.move_right();
+        cursor.move_right();
+
+        let row = cursor.slice(2..9);
+        assert_content!(row, "t x = 1");
+        assert_eq!(row.position(), 0);
+        assert_eq!(
+            row.highlights
+                .iter()
+                .map(|h| h.0.clone())
+                .collect::<Vec<_>>(),
+            [0..1, 6..7]
+        );
+        assert_eq!(cursor.slice(4..11).position(), 0);
+        cursor.move_down();
+        assert_eq!(cursor.slice(0..10).position(), 10);
+    }
+
+    #[test]
+    fn test_horizontal_window() {
+        let mut cursor = StringCursor::default();
+        cursor.extend("abcdefghij");
+        cursor.move_end();
+        // The cursor at the end and the marker on the left.
+        assert_eq!(cursor.horizontal_window(0, 6), 6..10);
+        cursor.move_home();
+        assert_eq!(cursor.horizontal_window(6, 6), 0..5);
+        for _ in 0..4 {
+            cursor.move_right();
+        }
+        // Only scrolls when the cursor leaves the window.
+        assert_eq!(cursor.horizontal_window(0, 6), 0..5);
+        cursor.move_right();
+        assert_eq!(cursor.horizontal_window(0, 6), 2..6);
+        assert_eq!(cursor.horizontal_window(0, 20), 0..10);
+
+        let mut cursor = StringCursor::default();
+        cursor.extend("漢字漢字");
+        cursor.move_home();
+        cursor.move_right();
+        cursor.move_right();
+        assert_eq!(cursor.horizontal_window(0, 6), 1..3);
+    }
+
+    #[test]
+    fn test_masked_cursor() {
+        let mut cursor = StringCursor::default();
+        cursor.set_mask(InputMask::new("(999) 999-9999"));
+        for c in "555a123".chars() {
+            cursor.insert(c);
+        }
+        assert_content!(cursor, "(555) 123");
+        cursor.move_left();
+        cursor.move_left();
+        cursor.move_left();
+        assert_cursor!(cursor, ')');
+        cursor.move_right();
+        assert_cursor!(cursor, '2');
+        cursor.move_left();
+        cursor.insert('4');
+        assert_content!(cursor, "(555) 412-3");
+        cursor.move_end();
+        for _ in 0..4 {
+            cursor.delete_left();
+        }
+        assert_content!(cursor, "(555");
+    }
 }
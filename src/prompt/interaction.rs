@@ -1,7 +1,11 @@
 use console::{Key, Term};
 use std::io::{self, Read, Write};
+use std::time::Duration;
 
 use super::cursor::StringCursor;
+use super::poll;
+use crate::output;
+use crate::transcript::{self, Entry};
 
 pub enum State<T> {
     Active,
@@ -16,7 +20,7 @@ pub enum Event {
 }
 
 /// Wraps text to fit the terminal width.
-fn wrap(text: &str, width: usize) -> String {
+pub(crate) fn wrap(text: &str, width: usize) -> String {
     use textwrap::{core::Word, fill, Options, WordSeparator};
 
     fill(
@@ -50,15 +54,42 @@ pub trait PromptInteraction<T> {
         None
     }
 
+    /// Returns an interval to re-render the prompt while waiting for a key,
+    /// e.g. while background results are being loaded. `None` by default:
+    /// the prompt is re-rendered only on key presses.
+    ///
+    /// Waiting for a key with a timeout is supported on Unix only, elsewhere
+    /// the prompt is re-rendered on key presses as well.
+    fn refresh_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Called when the refresh interval has elapsed without a key press
+    /// (see [`refresh_interval()`](PromptInteraction::refresh_interval)).
+    /// Returns a new state, e.g. once a background task has finished.
+    fn on_refresh(&mut self) -> Option<State<T>> {
+        None
+    }
+
     /// Whether features like Alt-Backspace and Alt-ArrowLeft/Right are allowed.
     /// Word editing is disabled for password prompts, for example.
     fn allow_word_editing(&self) -> bool {
         true
     }
 
-    /// Starts the interaction with the user via stderr.
+    /// Returns the prompt and the submitted answer to be recorded in the session
+    /// transcript (see [`set_transcript`](crate::set_transcript)).
+    fn transcript(&self) -> Option<(String, String)> {
+        None
+    }
+
+    /// Starts the interaction with the user via the terminal of the output
+    /// target (stderr by default, see [`set_output`](crate::set_output)).
     fn interact(&mut self) -> io::Result<T> {
-        self.interact_on(&mut Term::stderr())
+        match output::term() {
+            Some(mut term) => self.interact_on(&mut term),
+            None => Err(io::ErrorKind::NotConnected.into()),
+        }
     }
 
     /// Starts the interaction with the user via the given terminal.
@@ -93,12 +124,29 @@ pub trait PromptInteraction<T> {
             }
 
             match state {
-                State::Submit(result) => return Ok(result),
+                State::Submit(result) => {
+                    if let Some((prompt, answer)) = self.transcript() {
+                        transcript::record(Entry::Prompt { prompt, answer });
+                    }
+                    return Ok(result);
+                }
                 State::Cancel => return Err(io::ErrorKind::Interrupted.into()),
                 _ => {}
             }
 
-            match term.read_key() {
+            let key = match poll::read_key(term, self.refresh_interval()) {
+                // Re-render while waiting for a key.
+                Ok(None) => {
+                    if let Some(new_state) = self.on_refresh() {
+                        state = new_state;
+                    }
+                    continue;
+                }
+                Ok(Some(key)) => Ok(key),
+                Err(err) => Err(err),
+            };
+
+            match key {
                 Ok(Key::Escape) => {
                     state = State::Cancel;
 
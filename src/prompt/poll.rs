@@ -0,0 +1,73 @@
+//! Reading keys with a timeout, so that a prompt can be re-rendered while
+//! waiting (e.g. to show a loading indicator or background results).
+
+use std::io;
+use std::time::Duration;
+
+use console::{Key, Term};
+
+/// Reads a key waiting up to the timeout if it's given.
+///
+/// Returns `None` if the timeout has elapsed without a key press.
+/// Nothing is read in the background, so a key is never lost between prompts.
+pub(crate) fn read_key(term: &Term, timeout: Option<Duration>) -> io::Result<Option<Key>> {
+    match timeout {
+        Some(timeout) if !key_ready(timeout)? => Ok(None),
+        _ => term.read_key().map(Some),
+    }
+}
+
+/// Waits up to the timeout for a key press on the terminal input
+/// (the same one `console` reads keys from).
+#[cfg(unix)]
+fn key_ready(timeout: Duration) -> io::Result<bool> {
+    use std::fs::File;
+    use std::io::IsTerminal;
+    use std::os::fd::AsFd;
+
+    use nix::errno::Errno;
+    use nix::sys::select::{select, FdSet};
+    use nix::sys::termios::{cfmakeraw, tcgetattr, tcsetattr, SetArg};
+    use nix::sys::time::{TimeVal, TimeValLike};
+
+    let stdin = io::stdin();
+    let tty;
+    let fd = if stdin.is_terminal() {
+        stdin.as_fd()
+    } else {
+        tty = File::open("/dev/tty")?;
+        tty.as_fd()
+    };
+
+    // A key is available for reading only in the raw mode, otherwise the
+    // terminal waits for a whole line.
+    let original = tcgetattr(fd)?;
+    let mut raw = original.clone();
+    cfmakeraw(&mut raw);
+    raw.output_flags = original.output_flags;
+    tcsetattr(fd, SetArg::TCSADRAIN, &raw)?;
+
+    let mut fds = FdSet::new();
+    fds.insert(fd);
+    let mut timeout = TimeVal::microseconds(timeout.as_micros().try_into().unwrap_or(i64::MAX));
+    // Select (unlike poll) works with terminals on macOS as well.
+    let ready = select(None, &mut fds, None, None, &mut timeout);
+
+    // The mode is restored before reading the key, so Ctrl-C is handled
+    // by `console` as usual.
+    tcsetattr(fd, SetArg::TCSADRAIN, &original)?;
+
+    match ready {
+        Ok(count) => Ok(count > 0),
+        // A signal (e.g. the terminal is resized) is a reason to re-render.
+        Err(Errno::EINTR) => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Waiting for a key isn't supported: the key is read blocking, and the prompt
+/// is re-rendered on key presses only.
+#[cfg(not(unix))]
+fn key_ready(_timeout: Duration) -> io::Result<bool> {
+    Ok(true)
+}
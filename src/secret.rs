@@ -0,0 +1,52 @@
+use std::fmt::{self, Debug};
+
+use zeroize::Zeroizing;
+
+/// A secret text returned by the [`Password`](crate::Password) prompt.
+///
+/// The memory is zeroed when the secret is dropped, and the secret is not
+/// printed accidentally: there is no `Display`, and `Debug` shows `[REDACTED]`.
+/// Use [`SecretString::expose_secret`] to access the text.
+///
+/// ```
+/// use cliclack::password;
+///
+/// # fn test() -> std::io::Result<()> {
+/// let password = password("Provide a password").interact()?;
+/// let is_long = password.expose_secret().len() >= 12;
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
+#[derive(Clone, Default)]
+pub struct SecretString(Zeroizing<String>);
+
+impl SecretString {
+    pub(crate) fn new(secret: Zeroizing<String>) -> Self {
+        Self(secret)
+    }
+
+    /// Returns the secret text.
+    ///
+    /// Avoid copying it into a plain `String` which is not zeroed on drop.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns `true` if the secret is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self(Zeroizing::new(secret))
+    }
+}
+
+impl Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretString([REDACTED])")
+    }
+}
@@ -11,7 +11,7 @@ use crate::{
         interaction::{Event, PromptInteraction, State},
     },
     theme::THEME,
-    view::ListView,
+    view::{grouped_rows, ListView, Row},
 };
 
 #[derive(Clone)]
@@ -19,18 +19,25 @@ struct RadioButton<T> {
     value: T,
     label: String,
     hint: String,
+    group: Option<usize>,
+    disabled: bool,
 }
 
 impl<T> LabeledItem for RadioButton<T> {
     fn label(&self) -> &str {
         &self.label
     }
+
+    fn group(&self) -> Option<usize> {
+        self.group
+    }
 }
 
 /// A prompt that asks for one selection from a list of options.
 pub struct Select<T> {
     prompt: String,
     items: Vec<Rc<RefCell<RadioButton<T>>>>,
+    groups: Vec<String>,
     cursor: usize,
     initial_value: Option<T>,
     filter: FilteredView<RadioButton<T>>,
@@ -46,6 +53,7 @@ where
         Self {
             prompt: prompt.to_string(),
             items: Vec::new(),
+            groups: Vec::new(),
             cursor: 0,
             initial_value: None,
             filter: FilteredView::default(),
@@ -59,10 +67,22 @@ where
             value,
             label: label.to_string(),
             hint: hint.to_string(),
+            group: None,
+            disabled: false,
         })));
         self
     }
 
+    /// Adds an item which is shown but can't be chosen (dimmed and skipped
+    /// by the cursor), with a reason shown as a hint.
+    pub fn disabled_item(self, value: T, label: impl Display, reason: impl Display) -> Self {
+        let select = self.item(value, label, reason);
+        if let Some(item) = select.items.last() {
+            item.borrow_mut().disabled = true;
+        }
+        select
+    }
+
     /// Adds multiple items to the list of options.
     pub fn items(mut self, items: &[(T, impl Display, impl Display)]) -> Self {
         for (value, label, hint) in items {
@@ -71,6 +91,22 @@ where
         self
     }
 
+    /// Adds a group of items under a non-selectable section header.
+    ///
+    /// The groups are kept together while filtering, the items without
+    /// a group are listed before the groups.
+    pub fn group(mut self, label: impl Display, items: &[(T, impl Display, impl Display)]) -> Self {
+        let group = self.groups.len();
+        self.groups.push(label.to_string());
+
+        let start = self.items.len();
+        self = self.items(items);
+        for item in &self.items[start..] {
+            item.borrow_mut().group = Some(group);
+        }
+        self
+    }
+
     /// Sets the initially selected item by value.
     pub fn initial_value(mut self, value: T) -> Self {
         self.initial_value = Some(value);
@@ -104,6 +140,14 @@ where
                 "No items added to the list",
             ));
         }
+        if self.items.iter().all(|item| item.borrow().disabled) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "All items in the list are disabled",
+            ));
+        }
+        // The items without a group go first (the sort is stable).
+        self.items.sort_by_key(|item| item.borrow().group);
         if let Some(initial_value) = &self.initial_value {
             self.cursor = self
                 .items
@@ -112,10 +156,58 @@ where
                 .unwrap_or(self.cursor);
         }
         self.filter.set(self.items.to_vec());
+        self.skip_disabled();
+        self.scroll_to_cursor();
         <Self as PromptInteraction<T>>::interact(self)
     }
 }
 
+impl<T: Clone> Select<T> {
+    /// Returns the rows of the filtered items with the group headers.
+    fn rows(&self) -> Vec<Row> {
+        grouped_rows(self.filter.items().iter().map(|item| item.borrow().group))
+    }
+
+    /// Returns `true` if the filtered item can be chosen.
+    fn is_enabled(&self, index: usize) -> bool {
+        let item = self.filter.items().get(index);
+        item.is_some_and(|item| !item.borrow().disabled)
+    }
+
+    /// Moves the cursor to the next enabled item (or the previous one
+    /// if `up`), it stays if there is none.
+    fn move_cursor(&mut self, up: bool) {
+        let next = match up {
+            true => (0..self.cursor).rev().find(|&i| self.is_enabled(i)),
+            false => (self.cursor + 1..self.filter.items().len()).find(|&i| self.is_enabled(i)),
+        };
+        self.cursor = next.unwrap_or(self.cursor);
+        self.scroll_to_cursor();
+    }
+
+    /// Moves the cursor off a disabled item to the closest enabled one below
+    /// (or above if there is none).
+    fn skip_disabled(&mut self) {
+        if !self.is_enabled(self.cursor) {
+            let len = self.filter.items().len();
+            let below = (self.cursor..len).find(|&i| self.is_enabled(i));
+            let above = (0..self.cursor).rev().find(|&i| self.is_enabled(i));
+            self.cursor = below.or(above).unwrap_or(self.cursor);
+        }
+    }
+
+    /// Scrolls the list to the item under the cursor showing its group header.
+    fn scroll_to_cursor(&mut self) {
+        let rows = self.rows();
+        if let Some(row) = rows.iter().position(|row| *row == Row::Item(self.cursor)) {
+            if row > 0 && matches!(rows[row - 1], Row::Header(_)) {
+                self.view.scroll_to(row - 1);
+            }
+            self.view.scroll_to(row);
+        }
+    }
+}
+
 impl<T: Clone> PromptInteraction<T> for Select<T> {
     fn on(&mut self, event: &Event) -> State<T> {
         let Event::Key(key) = event;
@@ -124,27 +216,20 @@ impl<T: Clone> PromptInteraction<T> for Select<T> {
             if self.filter.items().is_empty() || self.cursor > self.filter.items().len() - 1 {
                 self.cursor = 0;
             }
+            self.skip_disabled();
+            self.scroll_to_cursor();
             return state;
         }
 
         match key {
             Key::ArrowUp | Key::ArrowLeft | Key::Char('k') | Key::Char('h') => {
-                if self.cursor > 0 {
-                    self.cursor -= 1;
-                }
-
-                if self.cursor < self.view.start {
-                    self.view.start = self.cursor;
-                }
+                self.move_cursor(true);
             }
             Key::ArrowDown | Key::ArrowRight | Key::Char('j') | Key::Char('l') => {
-                if !self.filter.items().is_empty() && self.cursor < self.filter.items().len() - 1 {
-                    self.cursor += 1;
-                }
-
-                if self.cursor >= self.view.start + self.view.height {
-                    self.view.start = self.cursor - self.view.height + 1;
-                }
+                self.move_cursor(false);
+            }
+            Key::Enter if !self.is_enabled(self.cursor) => {
+                return State::Error("No items available".into());
             }
             Key::Enter => {
                 return State::Submit(self.filter.items()[self.cursor].borrow().value.clone());
@@ -171,15 +256,25 @@ impl<T: Clone> PromptInteraction<T> for Select<T> {
         };
 
         let items_display: String = self
-            .filter
-            .items()
-            .iter()
-            .enumerate()
+            .rows()
+            .into_iter()
             .skip(self.view.start)
             .take(self.view.height)
-            .map(|(i, item)| {
-                let item = item.borrow();
-                theme.format_select_item(&state.into(), self.cursor == i, &item.label, &item.hint)
+            .map(|row| match row {
+                Row::Header(group) => theme.format_select_group(&state.into(), &self.groups[group]),
+                Row::Item(i) if self.filter.items()[i].borrow().disabled => {
+                    let item = self.filter.items()[i].borrow();
+                    theme.format_select_disabled_item(&state.into(), &item.label, &item.hint)
+                }
+                Row::Item(i) => {
+                    let item = self.filter.items()[i].borrow();
+                    theme.format_select_item(
+                        &state.into(),
+                        self.cursor == i,
+                        &item.label,
+                        &item.hint,
+                    )
+                }
             })
             .collect();
 
@@ -190,6 +285,11 @@ impl<T: Clone> PromptInteraction<T> for Select<T> {
     fn input(&mut self) -> Option<&mut StringCursor> {
         self.filter.input()
     }
+
+    fn transcript(&self) -> Option<(String, String)> {
+        let item = self.filter.items().get(self.cursor)?.borrow();
+        Some((self.prompt.clone(), item.label.clone()))
+    }
 }
 
 #[cfg(test)]
@@ -205,4 +305,63 @@ mod test {
             result.unwrap_err().to_string()
         );
     }
+
+    #[test]
+    fn all_disabled() {
+        let mut select = Select::new("Database")
+            .disabled_item("postgres", "PostgreSQL", "requires docker")
+            .disabled_item("mysql", "MySQL", "requires docker");
+        let err = select.interact().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(err.to_string(), "All items in the list are disabled");
+    }
+
+    #[test]
+    fn groups() {
+        let mut select = Select::new("Template")
+            .group("Frontend", &[("react", "React", ""), ("vue", "Vue", "")])
+            .group("Backend", &[("axum", "Axum", "")])
+            .max_rows(3);
+        select.filter.set(select.items.to_vec());
+
+        let rows = select.rows();
+        assert_eq!(rows[0], Row::Header(0));
+        assert_eq!(rows[3], Row::Header(1));
+
+        // The headers are skipped and scrolled into view.
+        select.on(&Event::Key(Key::ArrowDown));
+        select.on(&Event::Key(Key::ArrowDown));
+        assert_eq!(select.cursor, 2);
+        assert_eq!(select.view.start, 2);
+        select.on(&Event::Key(Key::ArrowUp));
+        select.on(&Event::Key(Key::ArrowUp));
+        assert_eq!(select.view.start, 0);
+        assert!(matches!(
+            select.on(&Event::Key(Key::Enter)),
+            State::Submit("react")
+        ));
+    }
+
+    #[test]
+    fn disabled_items() {
+        let mut select = Select::new("Database")
+            .disabled_item("postgres", "PostgreSQL", "requires docker")
+            .item("sqlite", "SQLite", "")
+            .disabled_item("mysql", "MySQL", "requires docker")
+            .item("memory", "In-memory", "");
+        select.filter.set(select.items.to_vec());
+        select.skip_disabled();
+        assert_eq!(select.cursor, 1);
+
+        select.on(&Event::Key(Key::ArrowUp));
+        assert_eq!(select.cursor, 1);
+        select.on(&Event::Key(Key::ArrowDown));
+        assert!(matches!(
+            select.on(&Event::Key(Key::Enter)),
+            State::Submit("memory")
+        ));
+
+        let frame = console::strip_ansi_codes(&select.render(&State::Active)).to_string();
+        assert!(frame.contains("PostgreSQL (requires docker)"));
+    }
 }
@@ -1,9 +1,11 @@
+use std::ops::Range;
 use std::sync::RwLock;
 
 use console::{style, Emoji, Style};
 use once_cell::sync::Lazy;
 use textwrap::core::display_width;
 
+use crate::password::Strength;
 use crate::prompt::{cursor::StringCursor, interaction::State};
 
 const S_STEP_ACTIVE: Emoji = Emoji("◆", "*");
@@ -31,6 +33,7 @@ const S_INFO: Emoji = Emoji("●", "•");
 const S_WARN: Emoji = Emoji("▲", "!");
 const S_ERROR: Emoji = Emoji("■", "x");
 
+/// Bold and underlined on, and off (without resetting the other attributes).
 const S_SPINNER: Emoji = Emoji("◒◐◓◑", "•oO0");
 const S_PROGRESS: Emoji = Emoji("■□", "#-");
 
@@ -57,6 +60,32 @@ impl<T> From<&State<T>> for ThemeState {
     }
 }
 
+/// Formats a range of the input characters with their highlighting styles
+/// (see [`StringCursor::set_highlights`]) or the given style otherwise.
+fn highlight(cursor: &StringCursor, range: Range<usize>, new_style: &Style) -> String {
+    let mut text = String::new();
+    for (run, style) in cursor.highlighted(range) {
+        let style = style.unwrap_or(new_style);
+        // Style each line separately not to spill over the frame bars.
+        for (i, line) in run.split('\n').enumerate() {
+            if i > 0 {
+                text.push('\n');
+            }
+            text.push_str(&style.apply_to(line).to_string());
+        }
+    }
+    text
+}
+
+/// Returns the character of a given spinner animation frame.
+fn spinner_frame(chars: &str, frame: usize) -> char {
+    let chars = chars.chars().collect::<Vec<_>>();
+    chars
+        .get(frame % chars.len().max(1))
+        .copied()
+        .unwrap_or(' ')
+}
+
 /// Defines rendering of the visual elements. By default, it implements the
 /// original [@Clack/prompts](https://www.npmjs.com/package/@clack/prompts) theme.
 ///
@@ -208,6 +237,17 @@ pub trait Theme {
         }
     }
 
+    /// Returns the console style of the inline completion hint (ghost text).
+    fn completion_style(&self, state: &ThemeState) -> Style {
+        self.placeholder_style(state)
+    }
+
+    /// Returns the console style of the error of the autocompletion provider,
+    /// the color of a validation error by default.
+    fn autocomplete_error_style(&self, _state: &ThemeState) -> Style {
+        self.bar_color(&ThemeState::Error(String::new()))
+    }
+
     /// Returns the console style of the placeholder text.
     fn placeholder_style(&self, state: &ThemeState) -> Style {
         match state {
@@ -218,13 +258,22 @@ pub trait Theme {
 
     /// Highlights the cursor character in the input text formatting the whole
     /// string with the given style.
+    ///
+    /// The highlighted parts of the input (see [`StringCursor::set_highlights`])
+    /// are styled with their own styles, the cursor character is always reversed.
     fn cursor_with_style(&self, cursor: &StringCursor, new_style: &Style) -> String {
-        let (left, cursor, right) = cursor.split();
+        let position = cursor.position();
+        let (_, cell, _) = cursor.split();
+        // The line break under the cursor stays in the right part.
+        let right = match cursor.current() {
+            Some(chr) if chr != '\n' => cursor.current_end(),
+            _ => position,
+        };
         format!(
             "{left}{cursor}{right}",
-            left = new_style.apply_to(left),
-            cursor = style(cursor).reverse(),
-            right = new_style.apply_to(right),
+            left = highlight(cursor, 0..position, new_style),
+            cursor = style(cell).reverse(),
+            right = highlight(cursor, right..cursor.len(), new_style),
         )
     }
 
@@ -301,7 +350,7 @@ pub trait Theme {
             })
         )
     }
-    
+
     /// Formats the input cursor with the given style adding frame bars around.
     ///
     /// It hides the cursor when the input is not active.
@@ -326,6 +375,124 @@ pub trait Theme {
         })
     }
 
+    /// Formats the masked input: the entered text followed by the remaining part
+    /// of the mask template in the placeholder style (see [`Theme::format_placeholder`]).
+    fn format_masked_input(
+        &self,
+        state: &ThemeState,
+        cursor: &StringCursor,
+        remaining: &str,
+    ) -> String {
+        let input = self.format_input(state, cursor);
+
+        match state {
+            ThemeState::Active | ThemeState::Error(_) if !remaining.is_empty() => format!(
+                "{input}{remaining}\n",
+                input = input.trim_end_matches('\n'),
+                remaining = self.placeholder_style(state).apply_to(remaining)
+            ),
+            _ => input,
+        }
+    }
+
+    /// Formats the input followed by the inline completion hint (ghost text),
+    /// the cursor is shown over the first character of the completion.
+    ///
+    /// It's used only if the cursor is at the end of a single-line input.
+    fn format_input_with_completion(
+        &self,
+        state: &ThemeState,
+        cursor: &StringCursor,
+        completion: &str,
+    ) -> String {
+        let mut chars = completion.chars();
+
+        match (state, chars.next()) {
+            (ThemeState::Active | ThemeState::Error(_), Some(first)) => format!(
+                "{bar}  {input}{first}{rest}\n",
+                bar = self.bar_color(state).apply_to(S_BAR),
+                input = highlight(cursor, 0..cursor.len(), &self.input_style(state)),
+                first = self.completion_style(state).reverse().apply_to(first),
+                rest = self.completion_style(state).apply_to(chars.as_str()),
+            ),
+            _ => self.format_input(state, cursor),
+        }
+    }
+
+    /// Formats a visual line (row) of the multiline input shown in the editor
+    /// viewport: a range of the input characters with the cursor if `active`.
+    ///
+    /// The line `number` is shown in the left gutter of a given width
+    /// (`None` for a continuation of a wrapped line), no gutter if the width is 0.
+    fn format_input_row(
+        &self,
+        state: &ThemeState,
+        cursor: &StringCursor,
+        row: Range<usize>,
+        active: bool,
+        number: Option<usize>,
+        gutter: usize,
+    ) -> String {
+        let new_style = &self.input_style(state);
+
+        let gutter = match (gutter, number) {
+            (0, _) => String::new(),
+            (width, number) => {
+                let number = number.map(|n| n.to_string()).unwrap_or_default();
+                format!(
+                    "{} ",
+                    self.placeholder_style(state)
+                        .apply_to(format!("{number:>width$}"))
+                )
+            }
+        };
+
+        let text = match active {
+            true => self.cursor_with_style(&cursor.slice(row), new_style),
+            false => highlight(cursor, row, new_style),
+        };
+
+        format!(
+            "{bar}  {gutter}{text}\n",
+            bar = self.bar_color(state).apply_to(S_BAR),
+        )
+    }
+
+    /// Formats the visible part of a long single-line input scrolled
+    /// horizontally to the cursor: a range of the input characters with
+    /// the markers of the text hidden on either side.
+    fn format_input_window(
+        &self,
+        state: &ThemeState,
+        cursor: &StringCursor,
+        window: Range<usize>,
+    ) -> String {
+        let marker = |hidden: bool| match hidden {
+            true => self.placeholder_style(state).apply_to("…").to_string(),
+            false => String::new(),
+        };
+        format!(
+            "{bar}  {left}{text}{right}\n",
+            bar = self.bar_color(state).apply_to(S_BAR),
+            left = marker(window.start > 0),
+            right = marker(window.end < cursor.len()),
+            text = self.cursor_with_style(&cursor.slice(window), &self.input_style(state)),
+        )
+    }
+
+    /// Formats the indicator of the multiline input lines hidden above
+    /// or below the editor viewport.
+    fn format_input_scroll(&self, state: &ThemeState, hidden: usize, above: bool) -> String {
+        let arrow = if above { "↑" } else { "↓" };
+        let lines = if hidden == 1 { "line" } else { "lines" };
+        format!(
+            "{}  {}\n",
+            self.bar_color(state).apply_to(S_BAR),
+            self.placeholder_style(state)
+                .apply_to(format!("{arrow} {hidden} more {lines}"))
+        )
+    }
+
     /// Formats an autocomplete suggestion item.
     fn format_autocomplete_item(&self, state: &ThemeState, item: &str, selected: bool) -> String {
         if selected {
@@ -344,6 +511,180 @@ pub trait Theme {
         }
     }
 
+    /// Highlights the characters of an autocomplete suggestion at the `matched`
+    /// positions (in chars) which match the input. The result is formatted
+    /// further with [`format_autocomplete_item`](Theme::format_autocomplete_item).
+    ///
+    /// By default, the matched characters are bold and underlined. Every run
+    /// of characters is styled on its own (with the style of the item), since
+    /// a styled run resets the style of the whole item at its end.
+    fn highlight_autocomplete_match(
+        &self,
+        state: &ThemeState,
+        item: &str,
+        matched: &[usize],
+        selected: bool,
+    ) -> String {
+        if matched.is_empty() {
+            return item.to_string();
+        }
+
+        let style = match selected {
+            true => self.bar_color(state),
+            false => self.input_style(state),
+        };
+        let match_style = style.clone().bold().underlined();
+
+        let mut highlighted = String::new();
+        let mut run = String::new();
+        let mut in_match = false;
+        for (ix, chr) in item.chars().enumerate() {
+            let is_matched = matched.contains(&ix);
+            if is_matched != in_match && !run.is_empty() {
+                let run_style = if in_match { &match_style } else { &style };
+                highlighted.push_str(&run_style.apply_to(&run).to_string());
+                run.clear();
+            }
+            in_match = is_matched;
+            run.push(chr);
+        }
+        let run_style = if in_match { &match_style } else { &style };
+        highlighted.push_str(&run_style.apply_to(&run).to_string());
+        highlighted
+    }
+
+    /// Formats an autocomplete suggestion with its description shown dimmed
+    /// in a column after the items of the given `width`.
+    ///
+    /// The characters of the item at the `matched` positions (in chars)
+    /// match the input, and they are highlighted
+    /// (see [`highlight_autocomplete_match`](Theme::highlight_autocomplete_match)).
+    fn format_autocomplete_suggestion(
+        &self,
+        state: &ThemeState,
+        item: &str,
+        matched: &[usize],
+        description: &str,
+        width: usize,
+        selected: bool,
+    ) -> String {
+        let highlighted = self.highlight_autocomplete_match(state, item, matched, selected);
+        let item_line = self.format_autocomplete_item(state, &highlighted, selected);
+
+        if description.is_empty() {
+            return item_line;
+        }
+
+        let padding = width.saturating_sub(display_width(item));
+        format!(
+            "{item_line}{}  {}",
+            " ".repeat(padding),
+            self.placeholder_style(state).apply_to(description)
+        )
+    }
+
+    /// Formats the indicator of the autocomplete suggestions hidden above
+    /// or below the visible part of the list.
+    fn format_autocomplete_scroll(&self, state: &ThemeState, hidden: usize, above: bool) -> String {
+        let arrow = if above { "↑" } else { "↓" };
+        format!(
+            "  {}  {}",
+            self.bar_color(state).apply_to(S_BAR),
+            self.placeholder_style(state)
+                .apply_to(format!("{arrow} {hidden} more"))
+        )
+    }
+
+    /// Formats the error of the autocompletion provider shown in the footer.
+    fn format_autocomplete_error(&self, state: &ThemeState, error: &str) -> String {
+        self.autocomplete_error_style(state)
+            .apply_to(error)
+            .to_string()
+    }
+
+    /// Formats the loading indicator shown in place of the autocomplete
+    /// suggestions, `frame` is a number of the spinner animation frame.
+    fn format_autocomplete_loading(&self, state: &ThemeState, frame: usize) -> String {
+        let spinner = spinner_frame(&self.spinner_chars(), frame);
+        format!(
+            "  {}  {}",
+            self.bar_color(state).apply_to(S_BAR),
+            self.placeholder_style(state)
+                .apply_to(format!("{spinner} Loading…"))
+        )
+    }
+
+    /// Formats the progress of the background validation shown in the footer
+    /// (see [`Input::validate_in_background`](crate::Input::validate_in_background)),
+    /// `frame` is a number of the spinner animation frame.
+    fn format_validation_progress(&self, state: &ThemeState, frame: usize) -> String {
+        let spinner = spinner_frame(&self.spinner_chars(), frame);
+        format!(
+            "{spinner} {}",
+            self.placeholder_style(state)
+                .apply_to("Validating… [Esc](Cancel)")
+        )
+    }
+
+    /// Formats the live character counter shown in the footer (see
+    /// [`Input::max_length`](crate::Input::max_length) and
+    /// [`Input::min_length`](crate::Input::min_length)), like `12/50`.
+    /// The `count` is a number of `char`s in the input.
+    ///
+    /// It's colored as an error near the maximum length and as a cancelled
+    /// prompt at the limit (yellow and red, see [`bar_color`](Theme::bar_color)),
+    /// and it's also shown after the error message (e.g. for a too short input).
+    fn format_character_count(
+        &self,
+        state: &ThemeState,
+        count: usize,
+        min: Option<usize>,
+        max: Option<usize>,
+    ) -> String {
+        let (text, color) = match max {
+            Some(max) if count >= max => (
+                format!("{count}/{max}"),
+                self.bar_color(&ThemeState::Cancel),
+            ),
+            // Near the limit: the last 10% of the characters.
+            Some(max) if count * 10 >= max * 9 => (
+                format!("{count}/{max}"),
+                self.bar_color(&ThemeState::Error(String::new())),
+            ),
+            Some(max) => (format!("{count}/{max}"), self.placeholder_style(state)),
+            None => (count.to_string(), self.placeholder_style(state)),
+        };
+        match min {
+            Some(min) if count < min => format!(
+                "{} {}",
+                color.apply_to(text),
+                self.placeholder_style(state)
+                    .apply_to(format!("(min {min})"))
+            ),
+            _ => color.apply_to(text).to_string(),
+        }
+    }
+
+    /// Formats the password strength meter shown under the input
+    /// (see [`Password::strength`](crate::Password::strength)).
+    fn format_password_strength(&self, state: &ThemeState, strength: Strength) -> String {
+        let color = match strength {
+            Strength::VeryWeak | Strength::Weak => Style::new().red(),
+            Strength::Fair => Style::new().yellow(),
+            Strength::Strong | Strength::VeryStrong => Style::new().green(),
+        };
+        let level = strength.level() + 1;
+        format!(
+            "{bar}  {filled}{empty} {label}\n",
+            bar = self.bar_color(state).apply_to(S_BAR),
+            filled = color.apply_to("■".repeat(level)),
+            empty = self
+                .placeholder_style(state)
+                .apply_to("□".repeat(5 - level)),
+            label = color.apply_to(strength),
+        )
+    }
+
     /// Formats the input cursor with the dimmed style of placeholder.
     ///
     /// Additionally:
@@ -486,6 +827,98 @@ pub trait Theme {
         )
     }
 
+    /// Formats a disabled item of the select list which can't be chosen,
+    /// the `reason` is shown as a hint.
+    ///
+    /// Hides the item on the submit and cancel states.
+    fn format_select_disabled_item(&self, state: &ThemeState, label: &str, reason: &str) -> String {
+        match state {
+            ThemeState::Cancel | ThemeState::Submit => String::new(),
+            _ => format!(
+                "{bar}  {radio} {label}\n",
+                bar = self.bar_color(state).apply_to(S_BAR),
+                radio = self.radio_symbol(state, false),
+                label = self.disabled_label(state, label, reason),
+            ),
+        }
+    }
+
+    /// Formats a disabled item of the multiselect list which can't be
+    /// selected, the `reason` is shown as a hint.
+    ///
+    /// Hides the item on the submit and cancel states.
+    fn format_multiselect_disabled_item(
+        &self,
+        state: &ThemeState,
+        label: &str,
+        reason: &str,
+    ) -> String {
+        match state {
+            ThemeState::Cancel | ThemeState::Submit => String::new(),
+            _ => format!(
+                "{bar}  {checkbox} {label}\n",
+                bar = self.bar_color(state).apply_to(S_BAR),
+                checkbox = self.checkbox_symbol(state, false, false),
+                label = self.disabled_label(state, label, reason),
+            ),
+        }
+    }
+
+    /// Returns the dimmed label of a disabled item with the reason.
+    fn disabled_label(&self, state: &ThemeState, label: &str, reason: &str) -> String {
+        let label = Style::new().dim().apply_to(label);
+        match reason {
+            "" => label.to_string(),
+            reason => format!(
+                "{label} {}",
+                self.placeholder_style(state)
+                    .apply_to(format!("({reason})"))
+            ),
+        }
+    }
+
+    /// Formats the header of a group of items in the select list
+    /// (see [`Select::group`](crate::Select::group)).
+    ///
+    /// Hides the header on the submit and cancel states.
+    fn format_select_group(&self, state: &ThemeState, label: &str) -> String {
+        match state {
+            ThemeState::Cancel | ThemeState::Submit => String::new(),
+            _ => format!(
+                "{bar}  {label}\n",
+                bar = self.bar_color(state).apply_to(S_BAR),
+                label = style(label).bold(),
+            ),
+        }
+    }
+
+    /// Formats the header of a group of items in the multiselect list
+    /// (see [`MultiSelect::group`](crate::MultiSelect::group)): `selected`
+    /// if all items of the group are selected, `active` when the cursor
+    /// highlights the header to toggle the whole group.
+    ///
+    /// Hides the header on the submit and cancel states.
+    fn format_multiselect_group(
+        &self,
+        state: &ThemeState,
+        label: &str,
+        selected: bool,
+        active: bool,
+    ) -> String {
+        match state {
+            ThemeState::Cancel | ThemeState::Submit => String::new(),
+            _ => format!(
+                "{bar}  {checkbox} {label}\n",
+                bar = self.bar_color(state).apply_to(S_BAR),
+                checkbox = self.checkbox_symbol(state, selected, active),
+                label = self
+                    .checkbox_style(state, selected, active)
+                    .bold()
+                    .apply_to(label),
+            ),
+        }
+    }
+
     /// Returns the full confirmation prompt rendering.
     fn format_confirm(&self, state: &ThemeState, confirm: bool) -> String {
         let yes = self.radio_item(state, confirm, "Yes", "");
@@ -733,4 +1166,24 @@ mod tests {
         // A simple backward compatibility check.
         ClackTheme.format_note("my prompt", "my message");
     }
+
+    #[test]
+    fn autocomplete_match() {
+        struct ArrowTheme;
+        impl Theme for ArrowTheme {
+            fn format_autocomplete_item(&self, _: &ThemeState, item: &str, _: bool) -> String {
+                format!("> {item}")
+            }
+        }
+
+        let line = ArrowTheme.format_autocomplete_suggestion(
+            &ThemeState::Active,
+            "serde",
+            &[0, 1],
+            "",
+            5,
+            false,
+        );
+        assert_eq!(console::strip_ansi_codes(&line), "> serde");
+    }
 }
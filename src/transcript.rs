@@ -0,0 +1,414 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use once_cell::sync::Lazy;
+
+/// The format of the session transcript.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum TranscriptFormat {
+    /// Plain text (default).
+    #[default]
+    Text,
+    /// Markdown.
+    Markdown,
+}
+
+/// A record of the prompt session without any styling (ANSI codes, frame bars).
+///
+/// Once set with [`set_transcript`], the transcript collects the intro title,
+/// each submitted prompt with its final answer (passwords are masked),
+/// log messages, notes, progress outcomes, and the outro message.
+///
+/// A failed write never fails the prompt (the answer isn't lost): the
+/// recording stops, and the error is returned by [`reset_transcript`].
+///
+/// ```
+/// use cliclack::{set_transcript, Transcript, TranscriptBuffer};
+///
+/// let buffer = TranscriptBuffer::default();
+/// set_transcript(Transcript::new(buffer.clone()).markdown());
+///
+/// cliclack::intro("create-my-app")?;
+/// cliclack::outro("You're all set!")?;
+///
+/// assert_eq!(buffer.contents(), "# create-my-app\n\nYou're all set!\n\n");
+/// cliclack::reset_transcript()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct Transcript {
+    writer: Box<dyn Write + Send>,
+    format: TranscriptFormat,
+    /// The first write error, the recording is stopped after it.
+    error: Option<io::Error>,
+}
+
+impl Transcript {
+    /// Creates a plain text transcript written into a given writer.
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            writer: Box::new(writer),
+            format: TranscriptFormat::default(),
+            error: None,
+        }
+    }
+
+    /// Creates a plain text transcript appended to a file (the file is
+    /// created if it doesn't exist).
+    pub fn file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file: File = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(file))
+    }
+
+    /// Sets the format of the transcript.
+    pub fn format(mut self, format: TranscriptFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Switches the transcript to the Markdown format.
+    pub fn markdown(self) -> Self {
+        self.format(TranscriptFormat::Markdown)
+    }
+
+    fn write(&mut self, entry: &Entry) -> io::Result<()> {
+        let text = match self.format {
+            TranscriptFormat::Text => entry.to_text(),
+            TranscriptFormat::Markdown => entry.to_markdown(),
+        };
+        self.writer.write_all(text.as_bytes())?;
+        self.writer.flush()
+    }
+}
+
+/// A shared in-memory buffer to collect the [`Transcript`].
+///
+/// The buffer is cheaply cloneable: pass one clone to [`Transcript::new`]
+/// and read the [`contents`](TranscriptBuffer::contents) with another one.
+#[derive(Clone, Default)]
+pub struct TranscriptBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl TranscriptBuffer {
+    /// Returns the transcript collected so far.
+    pub fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+    }
+}
+
+impl Write for TranscriptBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A single record of the transcript.
+pub(crate) enum Entry {
+    Intro(String),
+    Prompt {
+        prompt: String,
+        answer: String,
+    },
+    Log {
+        level: &'static str,
+        text: String,
+    },
+    Note {
+        prompt: String,
+        message: String,
+    },
+    Progress {
+        outcome: &'static str,
+        message: String,
+    },
+    Outro(String),
+    OutroCancel(String),
+}
+
+/// Removes styling and joins a multiline prompt into a single line.
+fn plain(text: &str) -> String {
+    console::strip_ansi_codes(text)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the plain prompt followed by a colon unless it already ends with
+/// a punctuation mark (e.g. `Name:` but `What is your name?`).
+fn label(prompt: &str) -> String {
+    let prompt = plain(prompt);
+    match prompt.chars().last() {
+        Some(chr) if chr.is_ascii_punctuation() => prompt,
+        _ => prompt + ":",
+    }
+}
+
+/// Returns a Markdown code fence longer than any run of backticks in the text.
+fn fence(text: &str) -> String {
+    let longest = text
+        .split(|chr| chr != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    "`".repeat(longest.max(2) + 1)
+}
+
+/// Escapes the Markdown syntax in a single line of text, so that e.g.
+/// `*foo*` or `# x` are recorded as typed.
+fn escape(line: &str) -> String {
+    // A list item number, like `1.` in `1. x`.
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let mut escaped = String::with_capacity(line.len());
+    for (ix, chr) in line.chars().enumerate() {
+        let block_start = match ix {
+            0 => "#>-+=".contains(chr),
+            ix => ix == digits && ".)".contains(chr),
+        };
+        if block_start || "\\`*_[]<>|~".contains(chr) {
+            escaped.push('\\');
+        }
+        escaped.push(chr);
+    }
+    escaped
+}
+
+/// Removes styling keeping the line structure of a message.
+fn plain_lines(text: &str) -> Vec<String> {
+    console::strip_ansi_codes(text)
+        .trim_matches('\n')
+        .lines()
+        .map(|line| line.trim_end().to_string())
+        .collect()
+}
+
+impl Entry {
+    fn to_text(&self) -> String {
+        let indented = |text: &str| {
+            plain_lines(text)
+                .iter()
+                .map(|line| format!("    {line}\n"))
+                .collect::<String>()
+        };
+
+        match self {
+            Entry::Intro(title) => {
+                let title = plain(title);
+                format!("{title}\n{}\n\n", "=".repeat(title.chars().count()))
+            }
+            Entry::Prompt { prompt, answer } if answer.contains('\n') => {
+                format!("{}\n{}", label(prompt), indented(answer))
+            }
+            Entry::Prompt { prompt, answer } => {
+                format!("{} {}\n", label(prompt), plain(answer))
+            }
+            Entry::Log { level, text } => format!("[{level}] {}\n", plain(text)),
+            Entry::Note { prompt, message } => {
+                format!("{}\n{}", label(prompt), indented(message))
+            }
+            Entry::Progress { outcome, message } => format!("[{outcome}] {}\n", plain(message)),
+            Entry::Outro(message) => format!("\n{}\n", plain(message)),
+            Entry::OutroCancel(message) => format!("\n[cancelled] {}\n", plain(message)),
+        }
+    }
+
+    fn to_markdown(&self) -> String {
+        match self {
+            Entry::Intro(title) => format!("# {}\n\n", escape(&plain(title))),
+            Entry::Prompt { prompt, answer } if answer.contains('\n') => {
+                let lines = plain_lines(answer);
+                let fence = fence(&lines.join("\n"));
+                let block = lines
+                    .iter()
+                    .map(|line| format!("  {line}\n"))
+                    .collect::<String>();
+                format!(
+                    "- **{}**\n\n  {fence}\n{block}  {fence}\n\n",
+                    escape(&label(prompt))
+                )
+            }
+            Entry::Prompt { prompt, answer } => {
+                format!(
+                    "- **{}** {}\n\n",
+                    escape(&label(prompt)),
+                    escape(&plain(answer))
+                )
+            }
+            Entry::Log { level, text } => format!("- _{level}_: {}\n\n", escape(&plain(text))),
+            Entry::Note { prompt, message } => {
+                let quote = plain_lines(message)
+                    .iter()
+                    .map(|line| format!("> {}\n", escape(line)).replace("> \n", ">\n"))
+                    .collect::<String>();
+                format!("> **{}**\n>\n{quote}\n", escape(&plain(prompt)))
+            }
+            Entry::Progress { outcome, message } => {
+                format!("- _{outcome}_: {}\n\n", escape(&plain(message)))
+            }
+            Entry::Outro(message) => format!("{}\n\n", escape(&plain(message))),
+            Entry::OutroCancel(message) => {
+                format!("_cancelled_: {}\n\n", escape(&plain(message)))
+            }
+        }
+    }
+}
+
+/// The global transcript (singleton).
+///
+/// It can be set with [`set_transcript`] function.
+static TRANSCRIPT: Lazy<Mutex<Option<Transcript>>> = Lazy::new(|| Mutex::new(None));
+
+/// Starts recording the session into a given transcript.
+///
+/// See [`reset_transcript`] for stopping the recording.
+pub fn set_transcript(transcript: Transcript) {
+    *TRANSCRIPT.lock().unwrap() = Some(transcript);
+}
+
+/// Stops recording the session transcript.
+///
+/// Returns the error if writing the transcript has failed (the entries
+/// after the failure aren't recorded).
+pub fn reset_transcript() -> io::Result<()> {
+    match TRANSCRIPT.lock().unwrap().take() {
+        Some(Transcript {
+            error: Some(err), ..
+        }) => Err(err),
+        _ => Ok(()),
+    }
+}
+
+/// Appends an entry to the transcript if it's been set.
+///
+/// A write error is kept for [`reset_transcript`] instead of failing the
+/// caller, e.g. a prompt which has already got the answer.
+pub(crate) fn record(entry: Entry) {
+    if let Some(transcript) = &mut *TRANSCRIPT.lock().unwrap() {
+        if transcript.error.is_none() {
+            transcript.error = transcript.write(&entry).err();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(format: TranscriptFormat, entries: Vec<Entry>) -> String {
+        let buffer = TranscriptBuffer::default();
+        let mut transcript = Transcript::new(buffer.clone()).format(format);
+        for entry in entries {
+            transcript.write(&entry).unwrap();
+        }
+        buffer.contents()
+    }
+
+    fn session() -> Vec<Entry> {
+        vec![
+            Entry::Intro(console::style("create-app").on_cyan().to_string()),
+            Entry::Prompt {
+                prompt: "Name".into(),
+                answer: "my-app".into(),
+            },
+            Entry::Prompt {
+                prompt: "Notes".into(),
+                answer: "line 1\nline 2".into(),
+            },
+            Entry::Log {
+                level: "info",
+                text: "Hello".into(),
+            },
+            Entry::Progress {
+                outcome: "done",
+                message: "Installed".into(),
+            },
+            Entry::Outro("Bye".into()),
+        ]
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(
+            render(TranscriptFormat::Text, session()),
+            "create-app\n==========\n\n\
+             Name: my-app\n\
+             Notes:\n    line 1\n    line 2\n\
+             [info] Hello\n\
+             [done] Installed\n\
+             \nBye\n"
+        );
+    }
+
+    #[test]
+    fn markdown() {
+        assert_eq!(
+            render(TranscriptFormat::Markdown, session()),
+            "# create-app\n\n\
+             - **Name:** my-app\n\n\
+             - **Notes:**\n\n  ```\n  line 1\n  line 2\n  ```\n\n\
+             - _info_: Hello\n\n\
+             - _done_: Installed\n\n\
+             Bye\n\n"
+        );
+    }
+
+    struct Failing;
+
+    impl Write for Failing {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn write_error() {
+        set_transcript(Transcript::new(Failing));
+        record(Entry::Outro("Bye".into()));
+        assert_eq!(reset_transcript().unwrap_err().to_string(), "disk full");
+        assert!(reset_transcript().is_ok());
+    }
+
+    #[test]
+    fn markdown_escape() {
+        let entries = vec![
+            Entry::Prompt {
+                prompt: "Pattern".into(),
+                answer: "*foo* [x](y)".into(),
+            },
+            Entry::Log {
+                level: "info",
+                text: "a_b `c`".into(),
+            },
+            Entry::Outro("# x".into()),
+            Entry::Outro("1. y".into()),
+        ];
+        assert_eq!(
+            render(TranscriptFormat::Markdown, entries),
+            "- **Pattern:** \\*foo\\* \\[x\\](y)\n\n\
+             - _info_: a\\_b \\`c\\`\n\n\
+             \\# x\n\n\
+             1\\. y\n\n"
+        );
+    }
+
+    #[test]
+    fn markdown_fence() {
+        let entry = Entry::Prompt {
+            prompt: "Snippet".into(),
+            answer: "```rust\nlet x = `1`;\n```".into(),
+        };
+        assert_eq!(
+            render(TranscriptFormat::Markdown, vec![entry]),
+            "- **Snippet:**\n\n  ````\n  ```rust\n  let x = `1`;\n  ```\n  ````\n\n"
+        );
+    }
+}
@@ -0,0 +1,351 @@
+use std::fmt::Display;
+use std::ops::{Bound, RangeBounds};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::{path::expand_tilde, validate::Validate};
+
+type Check = Arc<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+/// A reusable text validator for the [`Input`](crate::Input) and
+/// [`Password`](crate::Password) prompts.
+///
+/// There are presets for the common checks (like [`Validator::non_empty`],
+/// [`Validator::email`], or [`Validator::hostname`]) which can be combined with
+/// [`Validator::and`], [`Validator::or`], and [`Validator::not`], and the error
+/// message can be replaced with [`Validator::message`].
+///
+/// ```
+/// use cliclack::{Input, Validator};
+///
+/// # fn test() -> std::io::Result<()> {
+/// let name: String = Input::new("Project name")
+///     .validate(
+///         Validator::non_empty()
+///             .and(Validator::max_length(32))
+///             .and(Validator::hostname().message("Use letters, digits, and `-`")),
+///     )
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
+#[derive(Clone)]
+pub struct Validator {
+    check: Check,
+}
+
+impl Validator {
+    /// Creates a validator from a custom check returning an error message.
+    pub fn new<F>(check: F) -> Self
+    where
+        F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    {
+        Self {
+            check: Arc::new(check),
+        }
+    }
+
+    /// Creates a validator accepting the text if the predicate returns `true`.
+    fn from_predicate<F>(predicate: F, message: impl Display) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        let message = message.to_string();
+        Self::new(move |text| match predicate(text) {
+            true => Ok(()),
+            false => Err(message.clone()),
+        })
+    }
+
+    /// Requires a non-blank value (not only whitespace).
+    pub fn non_empty() -> Self {
+        Self::from_predicate(
+            |text| !text.trim().is_empty(),
+            "The value must not be empty",
+        )
+    }
+
+    /// Requires at least a given number of characters.
+    pub fn min_length(min: usize) -> Self {
+        Self::from_predicate(
+            move |text| text.chars().count() >= min,
+            format!("The value must be at least {min} characters long"),
+        )
+    }
+
+    /// Requires at most a given number of characters.
+    pub fn max_length(max: usize) -> Self {
+        Self::from_predicate(
+            move |text| text.chars().count() <= max,
+            format!("The value must be at most {max} characters long"),
+        )
+    }
+
+    /// Requires the whole value to match a regular expression
+    /// (see the [`regex`] crate for the syntax).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern is invalid, see [`Validator::try_regex`].
+    #[cfg(feature = "regex")]
+    pub fn regex(pattern: &str) -> Self {
+        Self::try_regex(pattern).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Requires the whole value to match a regular expression, or returns
+    /// an error if the pattern is invalid.
+    #[cfg(feature = "regex")]
+    pub fn try_regex(pattern: &str) -> Result<Self, regex::Error> {
+        let compiled = regex::Regex::new(&format!("^(?:{pattern})$"))?;
+        Ok(Self::from_predicate(
+            move |text| compiled.is_match(text),
+            format!("The value must match the pattern: {pattern}"),
+        ))
+    }
+
+    /// Requires the value to be one of given options.
+    pub fn one_of<I, S>(options: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Display,
+    {
+        let options: Vec<String> = options.into_iter().map(|opt| opt.to_string()).collect();
+        let message = format!("Expected one of: {}", options.join(", "));
+        Self::from_predicate(move |text| options.iter().any(|opt| opt == text), message)
+    }
+
+    /// Requires a number within a given range, e.g. `1..=65535`.
+    pub fn range<T, R>(range: R) -> Self
+    where
+        T: FromStr + PartialOrd + Display + Send + Sync + 'static,
+        R: RangeBounds<T> + Send + Sync + 'static,
+    {
+        let bound = |bound: Bound<&T>, inclusive: &str, exclusive: &str| match bound {
+            Bound::Included(value) => Some(format!("{inclusive} {value}")),
+            Bound::Excluded(value) => Some(format!("{exclusive} {value}")),
+            Bound::Unbounded => None,
+        };
+        let limits: Vec<String> = [
+            bound(range.start_bound(), "at least", "greater than"),
+            bound(range.end_bound(), "at most", "less than"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let message = format!("Expected a number {}", limits.join(" and "));
+
+        Self::new(move |text| match text.trim().parse::<T>() {
+            Ok(number) if range.contains(&number) => Ok(()),
+            Ok(_) => Err(message.clone()),
+            Err(_) => Err("Expected a number".to_string()),
+        })
+    }
+
+    /// Requires an email address like `user@example.com`.
+    pub fn email() -> Self {
+        Self::from_predicate(
+            |text| match text.rsplit_once('@') {
+                Some((local, domain)) => {
+                    !local.is_empty()
+                        && !local.contains(|c: char| c.is_whitespace() || c == '@')
+                        && domain.contains('.')
+                        && is_hostname(domain)
+                }
+                None => false,
+            },
+            "Expected an email address",
+        )
+    }
+
+    /// Requires a URL with a scheme and a host, like `https://example.com/path`.
+    pub fn url() -> Self {
+        Self::from_predicate(
+            |text| {
+                let Some((scheme, rest)) = text.split_once("://") else {
+                    return false;
+                };
+                let mut scheme = scheme.chars();
+                let valid_scheme = scheme.next().is_some_and(|c| c.is_ascii_alphabetic())
+                    && scheme.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+
+                // The authority: `user@host:port`.
+                let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+                let host_port = authority.rsplit('@').next().unwrap_or_default();
+                let host = match host_port.rsplit_once(':') {
+                    Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => host,
+                    _ => host_port,
+                };
+
+                valid_scheme
+                    && !text.contains(char::is_whitespace)
+                    && (is_hostname(host) || host.starts_with('[') && host.ends_with(']'))
+            },
+            "Expected a URL",
+        )
+    }
+
+    /// Requires a semantic version like `1.2.3`, `1.0.0-beta.1`, or `2.0.0+build.5`.
+    pub fn semver() -> Self {
+        Self::from_predicate(is_semver, "Expected a semantic version like 1.2.3")
+    }
+
+    /// Requires a hostname like `example.com` or `localhost`.
+    pub fn hostname() -> Self {
+        Self::from_predicate(is_hostname, "Expected a hostname")
+    }
+
+    /// Requires an existing path (a leading `~` is expanded into the home directory).
+    pub fn path_exists() -> Self {
+        Self::from_predicate(
+            |text| expand_tilde(text).exists(),
+            "The path does not exist",
+        )
+    }
+
+    /// Requires both validators to pass, the first error is returned.
+    pub fn and(self, other: Validator) -> Self {
+        Self::new(move |text| {
+            (self.check)(text)?;
+            (other.check)(text)
+        })
+    }
+
+    /// Requires any of the validators to pass, the error of the first one
+    /// is returned if both fail.
+    pub fn or(self, other: Validator) -> Self {
+        Self::new(move |text| match (self.check)(text) {
+            Ok(()) => Ok(()),
+            Err(err) => (other.check)(text).map_err(|_| err),
+        })
+    }
+
+    /// Requires the validator to fail, with a given error message otherwise.
+    pub fn not(self, message: impl Display) -> Self {
+        let message = message.to_string();
+        Self::new(move |text| match (self.check)(text) {
+            Ok(()) => Err(message.clone()),
+            Err(_) => Ok(()),
+        })
+    }
+
+    /// Replaces the error message.
+    pub fn message(self, message: impl Display) -> Self {
+        let message = message.to_string();
+        Self::new(move |text| (self.check)(text).map_err(|_| message.clone()))
+    }
+}
+
+impl Validate<String> for Validator {
+    type Err = String;
+
+    fn validate(&self, input: &String) -> Result<(), Self::Err> {
+        (self.check)(input)
+    }
+}
+
+/// Returns `true` for a valid hostname: dot-separated labels of ASCII
+/// letters, digits, and hyphens (not at the edges) up to 63 characters.
+fn is_hostname(text: &str) -> bool {
+    let text = text.strip_suffix('.').unwrap_or(text);
+    !text.is_empty()
+        && text.len() <= 253
+        && text.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+/// Returns `true` for a version following the Semantic Versioning 2.0.0.
+fn is_semver(text: &str) -> bool {
+    let (text, build) = match text.split_once('+') {
+        Some((text, build)) => (text, Some(build)),
+        None => (text, None),
+    };
+    let (version, pre) = match text.split_once('-') {
+        Some((version, pre)) => (version, Some(pre)),
+        None => (text, None),
+    };
+
+    let is_number = |part: &str| {
+        !part.is_empty()
+            && part.chars().all(|c| c.is_ascii_digit())
+            && (part == "0" || !part.starts_with('0'))
+    };
+    let is_identifiers = |text: &str, numeric_leading_zeros: bool| {
+        text.split('.').all(|id| {
+            !id.is_empty()
+                && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                && (numeric_leading_zeros
+                    || !id.chars().all(|c| c.is_ascii_digit())
+                    || is_number(id))
+        })
+    };
+
+    let parts: Vec<&str> = version.split('.').collect();
+    parts.len() == 3
+        && parts.iter().all(|part| is_number(part))
+        && pre.is_none_or(|pre| is_identifiers(pre, false))
+        && build.is_none_or(|build| is_identifiers(build, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(validator: &Validator, text: &str) -> Result<(), String> {
+        validator.validate(&text.to_string())
+    }
+
+    #[test]
+    fn presets() {
+        assert!(check(&Validator::non_empty(), " ").is_err());
+        assert!(check(&Validator::min_length(3), "ab").is_err());
+        assert!(check(&Validator::max_length(3), "abc").is_ok());
+        assert!(check(&Validator::one_of(["dev", "prod"]), "prod").is_ok());
+        assert_eq!(
+            check(&Validator::range(1..=65535), "70000"),
+            Err("Expected a number at least 1 and at most 65535".into())
+        );
+        assert!(check(&Validator::range(0.0..1.0), "0.5").is_ok());
+        assert!(check(&Validator::email(), "user@example.com").is_ok());
+        assert!(check(&Validator::email(), "user@localhost").is_err());
+        assert!(check(&Validator::url(), "https://user@example.com:8080/a?b#c").is_ok());
+        assert!(check(&Validator::url(), "example.com").is_err());
+        assert!(check(&Validator::semver(), "1.0.0-beta.1+build.005").is_ok());
+        assert!(check(&Validator::semver(), "1.02.0").is_err());
+        assert!(check(&Validator::semver(), "1.0.0-01").is_err());
+        assert!(check(&Validator::hostname(), "my-host.example.com").is_ok());
+        assert!(check(&Validator::hostname(), "-host").is_err());
+        assert!(check(&Validator::path_exists(), ".").is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn regex() {
+        assert!(check(&Validator::regex("[a-z-]+"), "my-name").is_ok());
+        assert!(check(&Validator::regex("a|b"), "ab").is_err());
+        // Nested quantifiers don't backtrack exponentially.
+        let nested = Validator::regex("(a*)*b");
+        assert!(check(&nested, &"a".repeat(64)).is_err());
+        assert!(Validator::try_regex("(unclosed").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn combinators() {
+        let slug = Validator::regex("[a-z-]+").message("Expected a slug");
+        let reserved = Validator::one_of(["admin"]).not("The name is reserved");
+        let name = slug.and(reserved);
+        assert_eq!(check(&name, "Admin"), Err("Expected a slug".into()));
+        assert_eq!(check(&name, "admin"), Err("The name is reserved".into()));
+        assert!(check(&name, "my-name").is_ok());
+
+        let address = Validator::hostname().or(Validator::email());
+        assert!(check(&address, "user@example.com").is_ok());
+        assert_eq!(check(&address, "a b"), Err("Expected a hostname".into()));
+    }
+}
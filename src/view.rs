@@ -15,3 +15,39 @@ impl Default for ListView {
         }
     }
 }
+
+impl ListView {
+    /// Scrolls the page to make a given row visible.
+    pub fn scroll_to(&mut self, row: usize) {
+        if row < self.start {
+            self.start = row;
+        } else if row >= self.start.saturating_add(self.height) {
+            self.start = row + 1 - self.height;
+        }
+    }
+}
+
+/// A row of a selection list: a group header or an item.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Row {
+    /// The header of a group by its index.
+    Header(usize),
+    /// An item by its index in the list.
+    Item(usize),
+}
+
+/// Returns the rows of a selection list given the group of each item
+/// (the items are sorted by groups): a header is shown before each group.
+pub(crate) fn grouped_rows(groups: impl IntoIterator<Item = Option<usize>>) -> Vec<Row> {
+    let mut rows = vec![];
+    let mut last = None;
+    for (i, group) in groups.into_iter().enumerate() {
+        match group {
+            Some(header) if group != last => rows.push(Row::Header(header)),
+            _ => {}
+        }
+        last = group;
+        rows.push(Row::Item(i));
+    }
+    rows
+}